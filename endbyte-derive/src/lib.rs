@@ -0,0 +1,381 @@
+//! `#[derive(Endianness)]` for the `endbyte` crate
+//!
+//! use it through the `derive` feature of `endbyte` instead of depending on
+//! this crate directly. the macro is written against `proc_macro` alone, so
+//! enabling it does not pull any third party crates into the build.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// derive `endbyte::Endianness` and `endbyte::EndianSerialize` for a struct,
+/// converting it field by field
+///
+/// every conversion method converts each field with that field's own
+/// `Endianness` impl, so nested structs that also derive it are converted
+/// recursively. the `EndianSerialize` impl serializes the fields back to back
+/// in declaration order, without padding, so every serialized field must
+/// implement `EndianSerialize` too.
+///
+/// fields marked `#[endian(skip)]` are left untouched by the conversions and
+/// are not serialized. `from_bytes` fills them with `Default::default()`.
+///
+/// named-field structs, tuple structs (converted by field index) and unit
+/// structs are supported. enums, unions and generic structs are rejected
+/// with a compile error.
+///
+/// with the `canonical-hash` feature the derive also emits an inherent
+/// `fn canonical_hash(&self) -> u64`, the fnv-1a hash of the big endian
+/// serialized form. it clones each serialized field, so those fields must
+/// implement `Clone`.
+#[proc_macro_derive(Endianness, attributes(endian))]
+pub fn derive_endianness(input: TokenStream) -> TokenStream {
+    match parse_struct(input) {
+        Ok(item) => expand(&item).parse().expect("derive output is valid rust"),
+        Err(message) => format!("::core::compile_error!({:?});", message)
+            .parse()
+            .expect("compile_error! invocation is valid rust"),
+    }
+}
+
+// how the fields of the struct are declared
+enum Shape {
+    Named,
+    Tuple,
+    Unit,
+}
+
+// a single struct field
+struct Field {
+    // field name, or its index for tuple structs
+    member: String,
+    // field type as written in the struct
+    ty: String,
+    // set by `#[endian(skip)]`
+    skip: bool,
+}
+
+// the parts of the struct the expansion needs
+struct Struct {
+    name: String,
+    shape: Shape,
+    fields: Vec<Field>,
+}
+
+fn parse_struct(input: TokenStream) -> Result<Struct, String> {
+    let mut tokens = input.into_iter();
+
+    // skip outer attributes and the visibility up to the `struct` keyword
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) => match ident.to_string().as_str() {
+                "struct" => break,
+                kind @ ("enum" | "union") => {
+                    return Err(format!(
+                        "`#[derive(Endianness)]` only supports structs, not {}s",
+                        kind
+                    ))
+                }
+                _ => {}
+            },
+            Some(_) => {}
+            None => return Err("`#[derive(Endianness)]` expected a struct".into()),
+        }
+    }
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err("`#[derive(Endianness)]` expected a struct name".into()),
+    };
+
+    let (shape, fields) = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+            (Shape::Named, parse_fields(group.stream(), true)?)
+        }
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            (Shape::Tuple, parse_fields(group.stream(), false)?)
+        }
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ';' => (Shape::Unit, Vec::new()),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+            return Err("`#[derive(Endianness)]` does not support generic structs".into())
+        }
+        _ => return Err("`#[derive(Endianness)]` could not parse the struct body".into()),
+    };
+
+    Ok(Struct {
+        name,
+        shape,
+        fields,
+    })
+}
+
+// split the field list on commas outside of angle brackets
+fn parse_fields(stream: TokenStream, named: bool) -> Result<Vec<Field>, String> {
+    let mut fields = Vec::new();
+    let mut current = Vec::new();
+    let mut depth = 0usize;
+    let mut after_dash = false;
+
+    for token in stream {
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                '<' => depth += 1,
+                // the `>` of a `->` arrow does not close a bracket
+                '>' if !after_dash => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    fields.push(parse_field(&current, fields.len(), named)?);
+                    current.clear();
+                    after_dash = false;
+                    continue;
+                }
+                _ => {}
+            }
+            after_dash = punct.as_char() == '-';
+        } else {
+            after_dash = false;
+        }
+        current.push(token);
+    }
+
+    if !current.is_empty() {
+        fields.push(parse_field(&current, fields.len(), named)?);
+    }
+
+    Ok(fields)
+}
+
+fn parse_field(tokens: &[TokenTree], index: usize, named: bool) -> Result<Field, String> {
+    let mut rest = tokens;
+    let mut skip = false;
+
+    // field attributes
+    while let [TokenTree::Punct(hash), TokenTree::Group(attr), tail @ ..] = rest {
+        if hash.as_char() != '#' || attr.delimiter() != Delimiter::Bracket {
+            break;
+        }
+        skip |= parse_endian_attr(attr.stream())?;
+        rest = tail;
+    }
+
+    // visibility, where `pub (u8, u16)` in a tuple struct is a public tuple field
+    if let [TokenTree::Ident(vis), tail @ ..] = rest {
+        if vis.to_string() == "pub" {
+            rest = tail;
+            if let [TokenTree::Group(scope), tail @ ..] = rest {
+                let restricted = scope.delimiter() == Delimiter::Parenthesis
+                    && matches!(
+                        scope.stream().into_iter().next(),
+                        Some(TokenTree::Ident(kw))
+                            if matches!(kw.to_string().as_str(), "crate" | "self" | "super" | "in")
+                    );
+                if restricted {
+                    rest = tail;
+                }
+            }
+        }
+    }
+
+    let member = if named {
+        match rest {
+            [TokenTree::Ident(name), TokenTree::Punct(colon), tail @ ..]
+                if colon.as_char() == ':' =>
+            {
+                rest = tail;
+                name.to_string()
+            }
+            _ => return Err("`#[derive(Endianness)]` expected a field name".into()),
+        }
+    } else {
+        index.to_string()
+    };
+
+    Ok(Field {
+        member,
+        ty: rest.iter().cloned().collect::<TokenStream>().to_string(),
+        skip,
+    })
+}
+
+// returns `true` for `endian(skip)` and `false` for unrelated attributes
+fn parse_endian_attr(stream: TokenStream) -> Result<bool, String> {
+    let mut tokens = stream.into_iter();
+
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "endian" => {}
+        _ => return Ok(false),
+    }
+
+    let args = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            group.stream().to_string()
+        }
+        _ => String::new(),
+    };
+
+    if args.trim() == "skip" {
+        Ok(true)
+    } else {
+        Err(format!(
+            "unknown endian attribute `{}`, expected `endian(skip)`",
+            args
+        ))
+    }
+}
+
+// build `Self { .. }`, `Self(..)` or `Self` from one expression per field
+fn construct(item: &Struct, values: impl Iterator<Item = String>) -> String {
+    match item.shape {
+        Shape::Named => {
+            let fields: Vec<String> = item
+                .fields
+                .iter()
+                .zip(values)
+                .map(|(field, value)| format!("{}: {}", field.member, value))
+                .collect();
+            format!("Self {{ {} }}", fields.join(", "))
+        }
+        Shape::Tuple => format!("Self({})", values.collect::<Vec<_>>().join(", ")),
+        Shape::Unit => "Self".into(),
+    }
+}
+
+fn expand(item: &Struct) -> String {
+    const TRAIT: &str = "::endbyte::Endianness";
+    const SERIALIZE: &str = "::endbyte::EndianSerialize";
+    const RESULT: &str = "::core::result::Result<Self, ::endbyte::EndianError>";
+
+    let mut body = String::new();
+
+    for method in [
+        "host_to_big_endian",
+        "host_to_little_endian",
+        "big_endian_to_host",
+        "little_endian_to_host",
+        "swap_endianness",
+    ] {
+        let values = item.fields.iter().map(|field| {
+            if field.skip {
+                format!("self.{}", field.member)
+            } else {
+                format!("{}::{}(self.{})", TRAIT, method, field.member)
+            }
+        });
+        body += &format!(
+            "#[inline] fn {}(self) -> Self {{ {} }}\n",
+            method,
+            construct(item, values)
+        );
+    }
+
+    let mut output = format!(
+        "#[automatically_derived] impl {} for {} {{ {} }}",
+        TRAIT, item.name, body
+    );
+
+    // byte offset of every field, as a constant expression
+    let mut body = String::new();
+    let mut offsets = Vec::new();
+    let mut offset = String::from("0");
+    for field in &item.fields {
+        offsets.push(offset.clone());
+        if !field.skip {
+            offset += &format!(" + <{} as {}>::BYTES", field.ty, SERIALIZE);
+        }
+    }
+
+    body += &format!("const BYTES: usize = {};\n", offset);
+
+    let eof = |len: &str| {
+        format!(
+            "if {len}.len() < <Self as {t}>::BYTES {{ \
+                return ::core::result::Result::Err(::endbyte::EndianError::UnexpectedEof {{ \
+                    needed: <Self as {t}>::BYTES, available: {len}.len() }}); }}",
+            len = len,
+            t = SERIALIZE
+        )
+    };
+
+    let values = item.fields.iter().zip(&offsets).map(|(field, offset)| {
+        if field.skip {
+            "::core::default::Default::default()".into()
+        } else {
+            format!(
+                "<{} as {}>::from_bytes(&bytes[{}..], order)?",
+                field.ty, SERIALIZE, offset
+            )
+        }
+    });
+    body += &format!(
+        "#[allow(unused_variables)] \
+         fn from_bytes(bytes: &[u8], order: ::endbyte::EndiannessType) -> {} {{ {} \
+         ::core::result::Result::Ok({}) }}\n",
+        RESULT,
+        eof("bytes"),
+        construct(item, values)
+    );
+
+    let writes: String = item
+        .fields
+        .iter()
+        .zip(&offsets)
+        .filter(|(field, _)| !field.skip)
+        .map(|(field, offset)| {
+            format!(
+                "{}::write_bytes(self.{}, &mut buf[{}..], order)?;",
+                SERIALIZE, field.member, offset
+            )
+        })
+        .collect();
+    body += &format!(
+        "#[allow(unused_variables)] \
+         fn write_bytes(self, buf: &mut [u8], order: ::endbyte::EndiannessType) \
+         -> ::core::result::Result<usize, ::endbyte::EndianError> {{ {} {} \
+         ::core::result::Result::Ok(<Self as {}>::BYTES) }}\n",
+        eof("buf"),
+        writes,
+        SERIALIZE
+    );
+
+    output += &format!(
+        "#[automatically_derived] impl {} for {} {{ {} }}",
+        SERIALIZE, item.name, body
+    );
+
+    if cfg!(feature = "canonical-hash") {
+        output += &expand_canonical_hash(item, &offsets);
+    }
+
+    output
+}
+
+// inherent `canonical_hash` hashing the big endian serialized fields
+fn expand_canonical_hash(item: &Struct, offsets: &[String]) -> String {
+    // the buffer holds exactly `BYTES`, so the writes cannot fail
+    let writes: String = item
+        .fields
+        .iter()
+        .zip(offsets)
+        .filter(|(field, _)| !field.skip)
+        .map(|(field, offset)| {
+            format!(
+                "let _ = ::endbyte::EndianSerialize::write_bytes(\
+                    ::core::clone::Clone::clone(&self.{}), &mut buf[{}..], \
+                    ::endbyte::EndiannessType::BigEndian);",
+                field.member, offset
+            )
+        })
+        .collect();
+
+    format!(
+        "#[automatically_derived] impl {name} {{ \
+            /// fnv-1a hash of the big endian serialized form, identical on every host \n\
+            #[allow(clippy::clone_on_copy)] \
+            pub fn canonical_hash(&self) -> u64 {{ \
+                #[allow(unused_mut)] \
+                let mut buf = [0u8; <{name} as ::endbyte::EndianSerialize>::BYTES]; \
+                {writes} \
+                ::endbyte::CanonicalHasher::hash_bytes(&buf) \
+            }} \
+        }}",
+        name = item.name,
+        writes = writes
+    )
+}
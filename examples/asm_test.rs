@@ -15,6 +15,23 @@ pub fn test_u64_conversion(val: u64) -> u64 {
     val.big_endian_to_host()
 }
 
+// swapping on little endian hosts, so the no-intrinsics build can be checked
+// for byte swap instructions
+#[no_mangle]
+pub fn test_u32_swap(val: u32) -> u32 {
+    val.host_to_big_endian()
+}
+
+#[no_mangle]
+pub fn test_u64_swap(val: u64) -> u64 {
+    val.host_to_big_endian()
+}
+
+#[no_mangle]
+pub fn test_u128_swap(val: u128) -> u128 {
+    val.host_to_big_endian()
+}
+
 fn main() {
     println!("assembly test functions compiled successfully");
 }
@@ -1,16 +1,33 @@
-#![no_std]
-#![no_main]
+// bare metal targets get a no_std binary with its own entry point and panic
+// handler. hosted targets, where std and its runtime are always linked, build
+// a plain program, so `cargo test --all-features` still builds the example
+#![cfg_attr(target_os = "none", no_std)]
+#![cfg_attr(target_os = "none", no_main)]
 
 use endbyte::Endianness;
 
+#[cfg(target_os = "none")]
 #[panic_handler]
 fn panic(_info: &core::panic::PanicInfo) -> ! {
     #[allow(clippy::empty_loop)]
     loop {}
 }
 
+#[cfg(target_os = "none")]
 #[no_mangle]
 pub extern "C" fn _start() -> ! {
+    exercise();
+
+    #[allow(clippy::empty_loop)]
+    loop {}
+}
+
+#[cfg(not(target_os = "none"))]
+fn main() {
+    exercise();
+}
+
+fn exercise() {
     // test the endbyte library in a no_std environment
 
     // test unsigned integers
@@ -90,7 +107,4 @@ pub extern "C" fn _start() -> ! {
         val_i32.host_to_little_endian().little_endian_to_host(),
         val_i32
     );
-
-    #[allow(clippy::empty_loop)]
-    loop {}
 }
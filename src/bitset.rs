@@ -0,0 +1,102 @@
+//! read-only view of a big endian bitmap
+
+/// a bitmap stored as one big endian integer of any length
+///
+/// the whole buffer is treated as a single big endian number, so bit 0 is the
+/// least significant bit of the last byte and bit `8 * len - 1` is the most
+/// significant bit of the first byte. bit `n` lives in byte `len - 1 - n / 8`
+/// under the mask `1 << (n % 8)`. a field made of several big endian words
+/// numbers its bits the same way, with the last word holding bits 0 to 31.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::BitSet;
+///
+/// let caps = BitSet::from_be_bytes(&[0x80, 0x00, 0x00, 0x05]);
+/// assert!(caps.contains(0));
+/// assert!(caps.contains(31));
+/// assert!(!caps.contains(1));
+/// assert!(caps.iter_set().eq([0, 2, 31]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitSet<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> BitSet<'a> {
+    /// view `bytes` as a big endian bitmap
+    pub const fn from_be_bytes(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// number of bits in the bitmap
+    pub const fn len(&self) -> usize {
+        self.bytes.len() * 8
+    }
+
+    /// whether the bitmap has no bits at all
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// whether `bit` is set. bits past the end of the bitmap are unset
+    pub fn contains(&self, bit: usize) -> bool {
+        let byte = match self.bytes.len().checked_sub(1 + bit / 8) {
+            Some(index) => self.bytes[index],
+            None => return false,
+        };
+        byte & (1 << (bit % 8)) != 0
+    }
+
+    /// iterate over the indices of the set bits in ascending order
+    pub fn iter_set(&self) -> impl Iterator<Item = usize> + 'a {
+        self.bytes
+            .iter()
+            .rev()
+            .enumerate()
+            .flat_map(|(index, &byte)| {
+                (0..8)
+                    .filter(move |bit| byte & (1 << bit) != 0)
+                    .map(move |bit| index * 8 + bit)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitset_word_boundaries() {
+        // two big endian u32 words: bits 32 and 63 in the first, 0 and 31 in the second
+        let bitmap = [0x80, 0x00, 0x00, 0x01, 0x80, 0x00, 0x00, 0x01];
+        let bits = BitSet::from_be_bytes(&bitmap);
+
+        assert_eq!(bits.len(), 64);
+        for bit in [0, 31, 32, 63] {
+            assert!(bits.contains(bit), "bit {} should be set", bit);
+        }
+        for bit in [1, 7, 8, 30, 33, 62, 64, 1000] {
+            assert!(!bits.contains(bit), "bit {} should be clear", bit);
+        }
+        assert!(bits.iter_set().eq([0, 31, 32, 63]));
+    }
+
+    #[test]
+    fn test_bitset_matches_integer() {
+        let value = 0x0102_8040u32;
+        let bytes = value.to_be_bytes();
+        let bits = BitSet::from_be_bytes(&bytes);
+
+        for bit in 0..32 {
+            assert_eq!(bits.contains(bit), value & (1 << bit) != 0);
+        }
+        assert!(bits.iter_set().eq([6, 15, 17, 24]));
+
+        let empty = BitSet::from_be_bytes(&[]);
+        assert!(empty.is_empty());
+        assert!(!empty.contains(0));
+        assert_eq!(empty.iter_set().count(), 0);
+    }
+}
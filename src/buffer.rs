@@ -0,0 +1,757 @@
+//! byte order helpers operating on untyped byte buffers
+
+use crate::{needs_swap, EndianError, EndianSerialize, Endianness, EndiannessType};
+
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, vec::Vec};
+
+/// reverse the byte order of every `width` sized chunk of `buf` in place
+///
+/// this works on raw bytes, which is handy for fixing up a buffer before it is
+/// reinterpreted as integers. `width` must be one of 2, 4, 8 or 16.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidWidth`] for an unsupported width and
+/// [`EndianError::MisalignedLength`] if `buf.len()` is not a multiple of `width`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::swap_buffer_in_place;
+///
+/// let mut buf = [0x12, 0x34, 0x56, 0x78];
+/// swap_buffer_in_place(&mut buf, 2).unwrap();
+/// assert_eq!(buf, [0x34, 0x12, 0x78, 0x56]);
+/// ```
+pub fn swap_buffer_in_place(buf: &mut [u8], width: usize) -> Result<(), EndianError> {
+    if !matches!(width, 2 | 4 | 8 | 16) {
+        return Err(EndianError::InvalidWidth(width));
+    }
+
+    if buf.len() % width != 0 {
+        return Err(EndianError::MisalignedLength {
+            len: buf.len(),
+            width,
+        });
+    }
+
+    for chunk in buf.chunks_exact_mut(width) {
+        chunk.reverse();
+    }
+
+    Ok(())
+}
+
+/// convert a buffer of 4 byte words to host order as declared by its first byte
+///
+/// the first byte declares the order of the rest of the buffer: `0` for big
+/// endian and `1` for little endian. the remaining words are swapped in place
+/// if that order differs from the host, and the payload after the flag byte is
+/// returned.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] for an empty buffer,
+/// [`EndianError::InvalidDiscriminant`] for an unknown flag byte and
+/// [`EndianError::MisalignedLength`] if the payload is not a whole number of
+/// words.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::normalize_to_host;
+///
+/// let mut buf = [0, 0x12, 0x34, 0x56, 0x78];
+/// let payload = normalize_to_host(&mut buf).unwrap();
+/// assert_eq!(u32::from_ne_bytes(payload.try_into().unwrap()), 0x12345678);
+/// ```
+pub fn normalize_to_host(buf: &mut [u8]) -> Result<&mut [u8], EndianError> {
+    let (flag, payload) = buf.split_first_mut().ok_or(EndianError::UnexpectedEof {
+        needed: 1,
+        available: 0,
+    })?;
+
+    let order = match flag {
+        0 => EndiannessType::BigEndian,
+        1 => EndiannessType::LittleEndian,
+        _ => return Err(EndianError::InvalidDiscriminant),
+    };
+
+    if payload.len() % 4 != 0 {
+        return Err(EndianError::MisalignedLength {
+            len: payload.len(),
+            width: 4,
+        });
+    }
+
+    if needs_swap(order) {
+        swap_buffer_in_place(payload, 4)?;
+    }
+
+    Ok(payload)
+}
+
+/// guess the byte order of a buffer of `u16` values known to be at most `max_expected`
+///
+/// every 2 byte word is decoded both ways and the order that keeps more values
+/// within `0..=max_expected` wins. returns `None` if both orders score the
+/// same, including for buffers shorter than one word. a trailing odd byte is
+/// ignored.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{guess_endianness_u16, EndiannessType};
+///
+/// // 12-bit sensor readings stored big endian
+/// let samples = [0x0f, 0xa0, 0x00, 0x10, 0x07, 0xff];
+/// assert_eq!(guess_endianness_u16(&samples, 4095), Some(EndiannessType::BigEndian));
+/// ```
+pub fn guess_endianness_u16(buf: &[u8], max_expected: u16) -> Option<EndiannessType> {
+    let mut big = 0usize;
+    let mut little = 0usize;
+
+    for word in buf.chunks_exact(2) {
+        let raw = u16::from_ne_bytes([word[0], word[1]]);
+        if raw.big_endian_to_host() <= max_expected {
+            big += 1;
+        }
+        if raw.little_endian_to_host() <= max_expected {
+            little += 1;
+        }
+    }
+
+    match big.cmp(&little) {
+        core::cmp::Ordering::Greater => Some(EndiannessType::BigEndian),
+        core::cmp::Ordering::Less => Some(EndiannessType::LittleEndian),
+        core::cmp::Ordering::Equal => None,
+    }
+}
+
+/// read `len` bytes at `offset` of a flash mirror through its aligned word
+///
+/// flash controllers that only allow 4 byte aligned reads return whole big
+/// endian words. this reads the aligned word containing `offset` and extracts
+/// the `len` requested bytes as a right aligned value, so the requested range
+/// must not cross a word boundary.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidWidth`] if `len` is zero or the range crosses
+/// into the next word and [`EndianError::UnexpectedEof`] if the aligned word
+/// extends past the end of `flash`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_flash_be;
+///
+/// let flash = [0x12, 0x34, 0x56, 0x78];
+/// assert_eq!(read_flash_be(&flash, 2, 1), Ok(0x56));
+/// assert_eq!(read_flash_be(&flash, 1, 2), Ok(0x3456));
+/// ```
+pub fn read_flash_be(flash: &[u8], offset: usize, len: usize) -> Result<u32, EndianError> {
+    let lane = offset % 4;
+    if len == 0 || lane + len > 4 {
+        return Err(EndianError::InvalidWidth(len));
+    }
+
+    let aligned = offset - lane;
+    let word = u32::from_bytes(
+        flash.get(aligned..).unwrap_or_default(),
+        EndiannessType::BigEndian,
+    )?;
+
+    // the first byte of a big endian word is its most significant byte
+    let shift = 8 * (4 - lane - len);
+    let mask = u32::MAX >> (8 * (4 - len));
+    Ok((word >> shift) & mask)
+}
+
+/// split a buffer into fixed size records padded to an alignment
+///
+/// each record takes `record_size` bytes and is followed by padding up to the
+/// next multiple of `align`. returns an iterator over the record slices,
+/// without their padding, and the trailing bytes that do not make up a whole
+/// padded record.
+///
+/// # panics
+///
+/// panics if `record_size` is zero, if `align` is zero, or if `record_size`
+/// rounded up to `align` overflows `usize`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::records_aligned;
+///
+/// let buf = [1, 2, 3, 0, 4, 5, 6, 0, 7];
+/// let (mut records, slack) = records_aligned(&buf, 3, 4);
+/// assert_eq!(records.next(), Some(&[1, 2, 3][..]));
+/// assert_eq!(records.next(), Some(&[4, 5, 6][..]));
+/// assert_eq!(records.next(), None);
+/// assert_eq!(slack, [7]);
+/// ```
+pub fn records_aligned(
+    buf: &[u8],
+    record_size: usize,
+    align: usize,
+) -> (impl Iterator<Item = &[u8]>, &[u8]) {
+    assert!(record_size > 0, "record size must be nonzero");
+    assert!(align > 0, "alignment must be nonzero");
+    let stride = record_size.next_multiple_of(align);
+
+    let chunks = buf.chunks_exact(stride);
+    let slack = chunks.remainder();
+    (chunks.map(move |chunk| &chunk[..record_size]), slack)
+}
+
+/// decode a buffer as `width` byte words in `order`, widened to `u64`
+///
+/// `width` must be one of 1, 2, 4 or 8.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidWidth`] for an unsupported width and
+/// [`EndianError::MisalignedLength`] if `buf.len()` is not a multiple of `width`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{read_words, EndiannessType};
+///
+/// let words = read_words(&[0x12, 0x34, 0x56, 0x78], 2, EndiannessType::BigEndian).unwrap();
+/// assert_eq!(words, [0x1234, 0x5678]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn read_words(
+    buf: &[u8],
+    width: usize,
+    order: EndiannessType,
+) -> Result<Vec<u64>, EndianError> {
+    let decode: fn(&[u8], EndiannessType) -> Result<u64, EndianError> = match width {
+        1 => |chunk, order| u8::from_bytes(chunk, order).map(u64::from),
+        2 => |chunk, order| u16::from_bytes(chunk, order).map(u64::from),
+        4 => |chunk, order| u32::from_bytes(chunk, order).map(u64::from),
+        8 => u64::from_bytes,
+        _ => return Err(EndianError::InvalidWidth(width)),
+    };
+
+    if buf.len() % width != 0 {
+        return Err(EndianError::MisalignedLength {
+            len: buf.len(),
+            width,
+        });
+    }
+
+    buf.chunks_exact(width)
+        .map(|chunk| decode(chunk, order))
+        .collect()
+}
+
+/// read a row-major matrix of big endian `u16` values with a byte stride
+///
+/// row `r` starts at byte `r * stride`, and each row holds `cols` values. any
+/// bytes between the end of a row and the start of the next are skipped, and
+/// the last row does not need trailing padding. returns the `rows * cols`
+/// values in row-major order.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidStride`] if `stride` is shorter than a row and
+/// [`EndianError::UnexpectedEof`] if `buf` is too short for the matrix.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_matrix_u16_be;
+///
+/// // two rows of one value, padded to 4 bytes per row
+/// let buf = [0x00, 0x01, 0xff, 0xff, 0x00, 0x02];
+/// assert_eq!(read_matrix_u16_be(&buf, 2, 1, 4).unwrap(), [1, 2]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn read_matrix_u16_be(
+    buf: &[u8],
+    rows: usize,
+    cols: usize,
+    stride: usize,
+) -> Result<Vec<u16>, EndianError> {
+    let row_len = cols.saturating_mul(2);
+    if stride < row_len {
+        return Err(EndianError::InvalidStride { stride, row_len });
+    }
+
+    let needed = match rows {
+        0 => 0,
+        _ => (rows - 1).saturating_mul(stride).saturating_add(row_len),
+    };
+    if buf.len() < needed {
+        return Err(EndianError::UnexpectedEof {
+            needed,
+            available: buf.len(),
+        });
+    }
+
+    let mut values = Vec::with_capacity(rows * cols);
+    for row in 0..rows {
+        let start = row * stride;
+        for value in buf[start..start + row_len].chunks_exact(2) {
+            values.push(u16::from_ne_bytes([value[0], value[1]]).big_endian_to_host());
+        }
+    }
+
+    Ok(values)
+}
+
+/// copy `buf` with every `width` sized chunk byte reversed
+///
+/// transcodes a buffer of big endian words into little endian words, or the
+/// other way round, without decoding them. `width` must be one of 2, 4, 8 or
+/// 16. use [`swap_buffer_in_place`] to avoid the copy.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidWidth`] for an unsupported width and
+/// [`EndianError::MisalignedLength`] if `buf.len()` is not a multiple of `width`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_and_reverse;
+///
+/// assert_eq!(read_and_reverse(&[0x12, 0x34, 0x56, 0x78], 4), Ok(vec![0x78, 0x56, 0x34, 0x12]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn read_and_reverse(buf: &[u8], width: usize) -> Result<Vec<u8>, EndianError> {
+    let mut reversed = buf.to_vec();
+    swap_buffer_in_place(&mut reversed, width)?;
+    Ok(reversed)
+}
+
+/// decode a big endian value of `width` bytes and format it as a rust literal
+///
+/// the literal is hexadecimal with an unsigned type suffix, such as
+/// `0x1234u16`, for generating fixtures and const tables from captured data.
+/// `width` must be one of 1, 2, 4, 8 or 16.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidWidth`] for an unsupported width and
+/// [`EndianError::UnexpectedEof`] if `buf` is shorter than `width`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::to_rust_literal_be;
+///
+/// assert_eq!(to_rust_literal_be(&[0x12, 0x34], 2).unwrap(), "0x1234u16");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn to_rust_literal_be(buf: &[u8], width: usize) -> Result<String, EndianError> {
+    let order = EndiannessType::BigEndian;
+    let (value, suffix) = match width {
+        1 => (u128::from(u8::from_bytes(buf, order)?), "u8"),
+        2 => (u128::from(u16::from_bytes(buf, order)?), "u16"),
+        4 => (u128::from(u32::from_bytes(buf, order)?), "u32"),
+        8 => (u128::from(u64::from_bytes(buf, order)?), "u64"),
+        16 => (u128::from_bytes(buf, order)?, "u128"),
+        _ => return Err(EndianError::InvalidWidth(width)),
+    };
+
+    Ok(format!("{:#x}{}", value, suffix))
+}
+
+/// list the offsets where two serialized buffers differ
+///
+/// returns `(offset, a[offset], b[offset])` for every differing byte, in
+/// offset order. only the common prefix is compared, so check the lengths
+/// separately. a field written in the wrong byte order shows up as a run of
+/// differences whose values mirror each other.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::diff_bytes;
+///
+/// let expected = [0x00, 0x00, 0x01, 0x02];
+/// let written = [0x00, 0x00, 0x02, 0x01];
+/// assert_eq!(diff_bytes(&expected, &written), [(2, 0x01, 0x02), (3, 0x02, 0x01)]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn diff_bytes(a: &[u8], b: &[u8]) -> Vec<(usize, u8, u8)> {
+    a.iter()
+        .zip(b)
+        .enumerate()
+        .filter(|(_, (x, y))| x != y)
+        .map(|(offset, (&x, &y))| (offset, x, y))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_buffer_width_4() {
+        let mut buf = [
+            0x00, 0x01, 0x02, 0x03, 0x10, 0x11, 0x12, 0x13, 0x20, 0x21, 0x22, 0x23, 0x30, 0x31,
+            0x32, 0x33,
+        ];
+        swap_buffer_in_place(&mut buf, 4).unwrap();
+
+        assert_eq!(
+            buf,
+            [
+                0x03, 0x02, 0x01, 0x00, 0x13, 0x12, 0x11, 0x10, 0x23, 0x22, 0x21, 0x20, 0x33, 0x32,
+                0x31, 0x30,
+            ]
+        );
+
+        // swapping twice restores the original layout
+        swap_buffer_in_place(&mut buf, 4).unwrap();
+        assert_eq!(buf[..4], [0x00, 0x01, 0x02, 0x03]);
+    }
+
+    #[test]
+    fn test_swap_buffer_matches_swap_bytes() {
+        let value = 0x123456789abcdef0u64;
+        let mut buf = value.to_ne_bytes();
+        swap_buffer_in_place(&mut buf, 8).unwrap();
+        assert_eq!(u64::from_ne_bytes(buf), value.swap_bytes());
+    }
+
+    #[test]
+    fn test_swap_buffer_errors() {
+        let mut buf = [0u8; 6];
+        assert_eq!(
+            swap_buffer_in_place(&mut buf, 4),
+            Err(EndianError::MisalignedLength { len: 6, width: 4 })
+        );
+        assert_eq!(
+            swap_buffer_in_place(&mut buf, 3),
+            Err(EndianError::InvalidWidth(3))
+        );
+        assert_eq!(
+            swap_buffer_in_place(&mut buf, 1),
+            Err(EndianError::InvalidWidth(1))
+        );
+    }
+
+    fn words(payload: &[u8]) -> [u32; 2] {
+        [
+            u32::from_ne_bytes(payload[..4].try_into().unwrap()),
+            u32::from_ne_bytes(payload[4..].try_into().unwrap()),
+        ]
+    }
+
+    #[test]
+    fn test_normalize_big_endian() {
+        let mut buf = [0x00, 0x12, 0x34, 0x56, 0x78, 0x00, 0x00, 0x00, 0x01];
+        let payload = normalize_to_host(&mut buf).unwrap();
+        assert_eq!(words(payload), [0x12345678, 1]);
+    }
+
+    #[test]
+    fn test_normalize_little_endian() {
+        let mut buf = [0x01, 0x78, 0x56, 0x34, 0x12, 0x01, 0x00, 0x00, 0x00];
+        let payload = normalize_to_host(&mut buf).unwrap();
+        assert_eq!(words(payload), [0x12345678, 1]);
+    }
+
+    #[test]
+    fn test_normalize_errors() {
+        assert_eq!(
+            normalize_to_host(&mut []),
+            Err(EndianError::UnexpectedEof {
+                needed: 1,
+                available: 0
+            })
+        );
+        assert_eq!(
+            normalize_to_host(&mut [2, 0, 0, 0, 0]),
+            Err(EndianError::InvalidDiscriminant)
+        );
+        assert_eq!(
+            normalize_to_host(&mut [0, 0, 0]),
+            Err(EndianError::MisalignedLength { len: 2, width: 4 })
+        );
+    }
+
+    #[test]
+    fn test_guess_endianness_u16() {
+        // 4000, 16, 2047 and 100 as big endian words
+        let big = [0x0f, 0xa0, 0x00, 0x10, 0x07, 0xff, 0x00, 0x64];
+        assert_eq!(
+            guess_endianness_u16(&big, 4095),
+            Some(EndiannessType::BigEndian)
+        );
+
+        let mut little = big;
+        swap_buffer_in_place(&mut little, 2).unwrap();
+        assert_eq!(
+            guess_endianness_u16(&little, 4095),
+            Some(EndiannessType::LittleEndian)
+        );
+    }
+
+    #[test]
+    fn test_guess_endianness_u16_ambiguous() {
+        // symmetric words are in range under both interpretations
+        assert_eq!(guess_endianness_u16(&[0x01, 0x01, 0x00, 0x00], 4095), None);
+        assert_eq!(guess_endianness_u16(&[0x01], 4095), None);
+        assert_eq!(guess_endianness_u16(&[], 4095), None);
+    }
+
+    #[test]
+    fn test_read_flash_be() {
+        let flash = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        // unaligned single bytes come out of their containing word
+        assert_eq!(read_flash_be(&flash, 5, 1), Ok(0x55));
+        assert_eq!(read_flash_be(&flash, 3, 1), Ok(0x33));
+        assert_eq!(read_flash_be(&flash, 6, 2), Ok(0x6677));
+        assert_eq!(read_flash_be(&flash, 4, 4), Ok(0x44556677));
+    }
+
+    #[test]
+    fn test_read_flash_be_errors() {
+        let flash = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55];
+
+        assert_eq!(
+            read_flash_be(&flash, 3, 2),
+            Err(EndianError::InvalidWidth(2))
+        );
+        assert_eq!(
+            read_flash_be(&flash, 0, 0),
+            Err(EndianError::InvalidWidth(0))
+        );
+        assert_eq!(
+            read_flash_be(&flash, 5, 1),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 2
+            })
+        );
+        assert_eq!(
+            read_flash_be(&flash, 9, 1),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_records_aligned() {
+        // two 6 byte records padded to 8 bytes, then 3 bytes of slack
+        let buf = [
+            0x00, 0x01, 0x00, 0x00, 0x00, 0x0a, 0xee, 0xee, // record 0
+            0x00, 0x02, 0x00, 0x00, 0x00, 0x14, 0xee, 0xee, // record 1
+            0x00, 0x03, 0x00,
+        ];
+
+        let (records, slack) = records_aligned(&buf, 6, 8);
+        let mut count = 0;
+        for (index, record) in records.enumerate() {
+            assert_eq!(record.len(), 6);
+            assert_eq!(
+                u16::from_bytes(record, EndiannessType::BigEndian),
+                Ok(index as u16 + 1)
+            );
+            assert_eq!(
+                u32::from_bytes(&record[2..], EndiannessType::BigEndian),
+                Ok(10 * (index as u32 + 1))
+            );
+            count += 1;
+        }
+
+        assert_eq!(count, 2);
+        assert_eq!(slack, [0x00, 0x03, 0x00]);
+    }
+
+    #[test]
+    fn test_records_aligned_exact() {
+        let (records, slack) = records_aligned(&[1, 2, 3, 4], 2, 1);
+        assert_eq!(records.count(), 2);
+        assert!(slack.is_empty());
+
+        let (records, slack) = records_aligned(&[1, 2], 4, 4);
+        assert_eq!(records.count(), 0);
+        assert_eq!(slack, [1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "record size must be nonzero")]
+    fn test_records_aligned_zero_record_size() {
+        let _ = records_aligned(&[1, 2, 3, 4], 0, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "alignment must be nonzero")]
+    fn test_records_aligned_zero_align() {
+        let _ = records_aligned(&[1, 2, 3, 4], 2, 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_read_words() {
+        let buf = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
+
+        assert_eq!(
+            read_words(&buf, 2, EndiannessType::BigEndian).unwrap(),
+            [0x1234, 0x5678, 0x9abc, 0xdef0]
+        );
+        assert_eq!(
+            read_words(&buf, 2, EndiannessType::LittleEndian).unwrap(),
+            [0x3412, 0x7856, 0xbc9a, 0xf0de]
+        );
+        assert_eq!(
+            read_words(&buf, 4, EndiannessType::BigEndian).unwrap(),
+            [0x12345678, 0x9abcdef0]
+        );
+        assert_eq!(
+            read_words(&buf, 4, EndiannessType::LittleEndian).unwrap(),
+            [0x78563412, 0xf0debc9a]
+        );
+        assert_eq!(
+            read_words(&buf, 8, EndiannessType::BigEndian).unwrap(),
+            [0x123456789abcdef0]
+        );
+        assert_eq!(
+            read_words(&buf[..2], 1, EndiannessType::BigEndian).unwrap(),
+            [0x12, 0x34]
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_read_words_errors() {
+        assert_eq!(
+            read_words(&[0; 6], 3, EndiannessType::BigEndian),
+            Err(EndianError::InvalidWidth(3))
+        );
+        assert_eq!(
+            read_words(&[0; 6], 4, EndiannessType::BigEndian),
+            Err(EndianError::MisalignedLength { len: 6, width: 4 })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_read_matrix_u16_be() {
+        // 2x3 matrix with two padding bytes after each row
+        let buf = [
+            0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0xee, 0xee, // row 0
+            0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0xee, 0xee, // row 1
+        ];
+
+        assert_eq!(
+            read_matrix_u16_be(&buf, 2, 3, 8).unwrap(),
+            [1, 2, 3, 0x100, 0x200, 0x300]
+        );
+
+        // the last row does not need its padding
+        assert_eq!(read_matrix_u16_be(&buf[..14], 2, 3, 8).unwrap().len(), 6);
+        assert!(read_matrix_u16_be(&buf, 0, 3, 8).unwrap().is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_read_matrix_u16_be_errors() {
+        let buf = [0u8; 16];
+
+        assert_eq!(
+            read_matrix_u16_be(&buf, 2, 3, 4),
+            Err(EndianError::InvalidStride {
+                stride: 4,
+                row_len: 6
+            })
+        );
+        assert_eq!(
+            read_matrix_u16_be(&buf, 3, 3, 8),
+            Err(EndianError::UnexpectedEof {
+                needed: 22,
+                available: 16
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_read_and_reverse() {
+        let words = [0x12345678u32, 0xdeadbeef, 1, u32::MAX - 1];
+
+        let mut big = [0u8; 16];
+        let mut little = [0u8; 16];
+        for (i, word) in words.iter().enumerate() {
+            big[i * 4..][..4].copy_from_slice(&word.to_be_bytes());
+            little[i * 4..][..4].copy_from_slice(&word.to_le_bytes());
+        }
+
+        assert_eq!(read_and_reverse(&big, 4).unwrap(), little);
+        assert_eq!(read_and_reverse(&little, 4).unwrap(), big);
+        assert_eq!(read_and_reverse(&big, 3), Err(EndianError::InvalidWidth(3)));
+        assert_eq!(
+            read_and_reverse(&big[..6], 4),
+            Err(EndianError::MisalignedLength { len: 6, width: 4 })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_to_rust_literal_be() {
+        assert_eq!(to_rust_literal_be(&[0x12, 0x34], 2).unwrap(), "0x1234u16");
+        assert_eq!(
+            to_rust_literal_be(&[0xde, 0xad, 0xbe, 0xef, 0xff], 4).unwrap(),
+            "0xdeadbeefu32"
+        );
+        assert_eq!(to_rust_literal_be(&[0x00], 1).unwrap(), "0x0u8");
+        assert_eq!(
+            to_rust_literal_be(&[0xff; 16], 16).unwrap(),
+            "0xffffffffffffffffffffffffffffffffu128"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_to_rust_literal_be_errors() {
+        assert_eq!(
+            to_rust_literal_be(&[0x12, 0x34, 0x56], 3),
+            Err(EndianError::InvalidWidth(3))
+        );
+        assert_eq!(
+            to_rust_literal_be(&[0x12], 2),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_diff_bytes() {
+        let mut expected = [0u8; 12];
+        let mut written = [0u8; 12];
+        for (i, value) in [1u32, 0x12345678, 3].into_iter().enumerate() {
+            expected[i * 4..][..4].copy_from_slice(&value.to_be_bytes());
+            written[i * 4..][..4].copy_from_slice(&value.to_be_bytes());
+        }
+        // the middle word was written in the wrong order
+        written[4..8].copy_from_slice(&0x12345678u32.to_le_bytes());
+
+        assert_eq!(
+            diff_bytes(&expected, &written),
+            [
+                (4, 0x12, 0x78),
+                (5, 0x34, 0x56),
+                (6, 0x56, 0x34),
+                (7, 0x78, 0x12)
+            ]
+        );
+        assert!(diff_bytes(&expected, &expected).is_empty());
+        assert!(diff_bytes(&expected[..4], &expected).is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+//! conversions between integers and fixed size byte arrays
+
+use crate::{EndianError, Endianness};
+
+/// integers that convert to and from byte arrays in a given byte order
+///
+/// this is the allocation free counterpart of calling a conversion method and
+/// then `to_ne_bytes`, with the array size carried by the [`Bytes`]
+/// associated type.
+///
+/// [`Bytes`]: EndianBytes::Bytes
+///
+/// # examples
+///
+/// ```
+/// use endbyte::EndianBytes;
+///
+/// assert_eq!(0x1234u16.to_big_endian_bytes(), [0x12, 0x34]);
+/// assert_eq!(u32::from_little_endian_bytes([0x78, 0x56, 0x34, 0x12]), 0x12345678);
+/// ```
+pub trait EndianBytes: Endianness + Sized {
+    /// byte array holding the serialized value, `[u8; N]` for an `N` byte type
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Copy;
+
+    /// serialize in big endian byte order
+    fn to_big_endian_bytes(self) -> Self::Bytes;
+
+    /// serialize in little endian byte order
+    fn to_little_endian_bytes(self) -> Self::Bytes;
+
+    /// deserialize from big endian byte order
+    fn from_big_endian_bytes(bytes: Self::Bytes) -> Self;
+
+    /// deserialize from little endian byte order
+    fn from_little_endian_bytes(bytes: Self::Bytes) -> Self;
+}
+
+macro_rules! impl_endian_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl EndianBytes for $t {
+                type Bytes = [u8; core::mem::size_of::<$t>()];
+
+                #[inline]
+                fn to_big_endian_bytes(self) -> Self::Bytes {
+                    self.host_to_big_endian().to_ne_bytes()
+                }
+
+                #[inline]
+                fn to_little_endian_bytes(self) -> Self::Bytes {
+                    self.host_to_little_endian().to_ne_bytes()
+                }
+
+                #[inline]
+                fn from_big_endian_bytes(bytes: Self::Bytes) -> Self {
+                    <$t>::from_ne_bytes(bytes).big_endian_to_host()
+                }
+
+                #[inline]
+                fn from_little_endian_bytes(bytes: Self::Bytes) -> Self {
+                    <$t>::from_ne_bytes(bytes).little_endian_to_host()
+                }
+            }
+        )*
+    };
+}
+
+impl_endian_bytes!(u8, u16, u32, u64, u128, usize);
+impl_endian_bytes!(i8, i16, i32, i64, i128, isize);
+
+// `char` has no `Endianness` impl on purpose: swapping the bytes of a scalar
+// value rarely yields another scalar value, so the conversion methods could
+// not return `Self`. these helpers swap the `u32` bit pattern instead and
+// validate it only when turning it back into a `char`.
+
+/// serialize a `char` as a big endian 32-bit scalar value
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{char_from_be_bytes, char_to_be_bytes};
+///
+/// assert_eq!(char_to_be_bytes('\u{1f980}'), [0x00, 0x01, 0xf9, 0x80]);
+/// assert_eq!(char_from_be_bytes([0x00, 0x00, 0x00, 0x41]), Ok('A'));
+/// ```
+#[inline]
+pub fn char_to_be_bytes(c: char) -> [u8; 4] {
+    (c as u32).to_big_endian_bytes()
+}
+
+/// serialize a `char` as a little endian 32-bit scalar value
+#[inline]
+pub fn char_to_le_bytes(c: char) -> [u8; 4] {
+    (c as u32).to_little_endian_bytes()
+}
+
+/// deserialize a `char` from a big endian 32-bit scalar value
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidChar`] if the value is a surrogate or above
+/// `0x10ffff`.
+#[inline]
+pub fn char_from_be_bytes(bytes: [u8; 4]) -> Result<char, EndianError> {
+    let value = u32::from_big_endian_bytes(bytes);
+    char::from_u32(value).ok_or(EndianError::InvalidChar(value))
+}
+
+/// deserialize a `char` from a little endian 32-bit scalar value
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidChar`] if the value is a surrogate or above
+/// `0x10ffff`.
+#[inline]
+pub fn char_from_le_bytes(bytes: [u8; 4]) -> Result<char, EndianError> {
+    let value = u32::from_little_endian_bytes(bytes);
+    char::from_u32(value).ok_or(EndianError::InvalidChar(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // round-trip `value` through both orders
+    fn round_trip<T: EndianBytes + Copy + PartialEq + core::fmt::Debug>(value: T) {
+        assert_eq!(T::from_big_endian_bytes(value.to_big_endian_bytes()), value);
+        assert_eq!(
+            T::from_little_endian_bytes(value.to_little_endian_bytes()),
+            value
+        );
+    }
+
+    // round-trip each value and check its bytes against core
+    macro_rules! assert_round_trip {
+        ($($value:expr),*) => {
+            $(
+                let value = $value;
+                round_trip(value);
+                assert_eq!(value.to_big_endian_bytes(), value.to_be_bytes());
+                assert_eq!(value.to_little_endian_bytes(), value.to_le_bytes());
+            )*
+        };
+    }
+
+    #[test]
+    fn test_round_trip_unsigned() {
+        assert_round_trip!(
+            0xabu8,
+            0x1234u16,
+            0x12345678u32,
+            0x0102030405060708u64,
+            0x0102030405060708090a0b0c0d0e0f10u128,
+            0x1234usize
+        );
+    }
+
+    #[test]
+    fn test_round_trip_signed() {
+        assert_round_trip!(
+            -2i8,
+            -0x1234i16,
+            -0x12345678i32,
+            i64::MIN + 1,
+            -1i128 << 100,
+            -0x1234isize
+        );
+    }
+
+    #[test]
+    fn test_byte_layout() {
+        assert_eq!(0x1234u16.to_big_endian_bytes(), [0x12, 0x34]);
+        assert_eq!(0x1234u16.to_little_endian_bytes(), [0x34, 0x12]);
+        assert_eq!((-2i32).to_big_endian_bytes(), [0xff, 0xff, 0xff, 0xfe]);
+        assert_eq!(
+            u64::from_big_endian_bytes([0, 0, 0, 0, 0, 0, 0x01, 0x00]),
+            256
+        );
+    }
+
+    #[test]
+    fn test_char_round_trip() {
+        for c in ['\0', 'A', '\u{e9}', '\u{20ac}', '\u{1f980}', char::MAX] {
+            assert_eq!(char_from_be_bytes(char_to_be_bytes(c)), Ok(c));
+            assert_eq!(char_from_le_bytes(char_to_le_bytes(c)), Ok(c));
+        }
+
+        assert_eq!(char_to_be_bytes('\u{1f980}'), [0x00, 0x01, 0xf9, 0x80]);
+        assert_eq!(char_to_le_bytes('\u{1f980}'), [0x80, 0xf9, 0x01, 0x00]);
+    }
+
+    #[test]
+    fn test_char_invalid_scalar() {
+        // reading big endian bytes in the wrong order gives 0x80f90100
+        assert_eq!(
+            char_from_le_bytes(char_to_be_bytes('\u{1f980}')),
+            Err(EndianError::InvalidChar(0x80f9_0100))
+        );
+        assert_eq!(
+            char_from_be_bytes([0x00, 0x00, 0xd8, 0x00]),
+            Err(EndianError::InvalidChar(0xd800))
+        );
+        assert_eq!(
+            char_from_be_bytes([0x00, 0x11, 0x00, 0x00]),
+            Err(EndianError::InvalidChar(0x110000))
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! `const fn` conversions for compile time tables
+//!
+//! trait methods cannot be called in const contexts on stable rust, so the
+//! [`Endianness`](crate::Endianness) trait cannot be used to build `const` or
+//! `static` values until const traits are available. these free functions
+//! cover the same conversions and can be used anywhere a constant is needed.
+//!
+//! ```
+//! use endbyte::{u16_from_be, u32_to_be};
+//!
+//! const MAGIC: u32 = u32_to_be(0x7f454c46);
+//! const PORTS: [u16; 2] = [u16_from_be(0x5000), u16_from_be(0xbb01)];
+//!
+//! assert_eq!(MAGIC.to_ne_bytes(), *b"\x7fELF");
+//! assert_eq!(PORTS, [80, 443]);
+//! ```
+
+macro_rules! impl_const_conversions {
+    ($($t:ty => $to_be:ident, $to_le:ident, $from_be:ident, $from_le:ident;)*) => {
+        $(
+            #[doc = concat!("convert a `", stringify!($t), "` from host byte order to big endian")]
+            #[inline]
+            pub const fn $to_be(value: $t) -> $t {
+                if cfg!(target_endian = "little") {
+                    value.swap_bytes()
+                } else {
+                    value
+                }
+            }
+
+            #[doc = concat!("convert a `", stringify!($t), "` from host byte order to little endian")]
+            #[inline]
+            pub const fn $to_le(value: $t) -> $t {
+                if cfg!(target_endian = "big") {
+                    value.swap_bytes()
+                } else {
+                    value
+                }
+            }
+
+            #[doc = concat!("convert a `", stringify!($t), "` from big endian to host byte order")]
+            #[inline]
+            pub const fn $from_be(value: $t) -> $t {
+                $to_be(value)
+            }
+
+            #[doc = concat!("convert a `", stringify!($t), "` from little endian to host byte order")]
+            #[inline]
+            pub const fn $from_le(value: $t) -> $t {
+                $to_le(value)
+            }
+        )*
+    };
+}
+
+impl_const_conversions! {
+    u16 => u16_to_be, u16_to_le, u16_from_be, u16_from_le;
+    u32 => u32_to_be, u32_to_le, u32_from_be, u32_from_le;
+    u64 => u64_to_be, u64_to_le, u64_from_be, u64_from_le;
+    u128 => u128_to_be, u128_to_le, u128_from_be, u128_from_le;
+    i16 => i16_to_be, i16_to_le, i16_from_be, i16_from_le;
+    i32 => i32_to_be, i32_to_le, i32_from_be, i32_from_le;
+    i64 => i64_to_be, i64_to_le, i64_from_be, i64_from_le;
+    i128 => i128_to_be, i128_to_le, i128_from_be, i128_from_le;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Endianness;
+
+    #[test]
+    fn test_const_evaluation() {
+        const BE: [u8; 4] = u32_to_be(0x12345678).to_ne_bytes();
+        const LE: [u8; 4] = u32_to_le(0x12345678).to_ne_bytes();
+        const TABLE: [u16; 3] = {
+            let mut table = [0u16; 3];
+            let mut i = 0;
+            while i < table.len() {
+                table[i] = u16_to_be(i as u16 + 1);
+                i += 1;
+            }
+            table
+        };
+
+        assert_eq!(BE, [0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(LE, [0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(TABLE[2].to_ne_bytes(), [0x00, 0x03]);
+        assert_eq!(const { i64_from_be(i64_to_be(-5)) }, -5);
+    }
+
+    #[test]
+    fn test_const_matches_trait() {
+        assert_eq!(u16_to_be(0x1234), 0x1234u16.host_to_big_endian());
+        assert_eq!(u64_to_le(0x0102), 0x0102u64.host_to_little_endian());
+        assert_eq!(u128_from_be(7), 7u128.big_endian_to_host());
+        assert_eq!(i32_from_le(-7), (-7i32).little_endian_to_host());
+        assert_eq!(i16_to_be(-2), (-2i16).host_to_big_endian());
+        assert_eq!(i128_from_le(i128::MIN), i128::MIN.little_endian_to_host());
+        assert_eq!(u32_from_le(u32_to_le(9)), 9);
+    }
+}
@@ -0,0 +1,257 @@
+//! reading and writing fieldless enums through their discriminant
+
+use crate::{EndianError, EndianSerialize, EndiannessType};
+
+/// a fieldless enum that is serialized as its primitive discriminant
+///
+/// implement it with [`impl_enum_repr!`](crate::impl_enum_repr) for enums
+/// with an explicit `#[repr]`.
+pub trait EnumRepr: Sized {
+    /// the primitive type of the discriminant
+    type Repr: EndianSerialize;
+
+    /// return the discriminant of `self`
+    fn to_repr(self) -> Self::Repr;
+
+    /// return the variant with the given discriminant, if any
+    fn from_repr(repr: Self::Repr) -> Option<Self>;
+}
+
+/// implement [`EnumRepr`] for a fieldless enum by listing its variants
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{impl_enum_repr, EnumRepr};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// #[repr(u16)]
+/// enum Kind {
+///     Data = 1,
+///     Ack = 2,
+/// }
+///
+/// impl_enum_repr!(Kind: u16 { Data, Ack });
+///
+/// assert_eq!(Kind::Ack.to_repr(), 2);
+/// assert_eq!(Kind::from_repr(1), Some(Kind::Data));
+/// assert_eq!(Kind::from_repr(3), None);
+/// ```
+#[macro_export]
+macro_rules! impl_enum_repr {
+    ($name:ident : $repr:ty { $($variant:ident),* $(,)? }) => {
+        impl $crate::EnumRepr for $name {
+            type Repr = $repr;
+
+            fn to_repr(self) -> $repr {
+                self as $repr
+            }
+
+            fn from_repr(repr: $repr) -> Option<Self> {
+                $(
+                    if repr == $name::$variant as $repr {
+                        return Some($name::$variant);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
+/// write the discriminant of `value` in big endian to the front of `buf`
+///
+/// returns the number of bytes written.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `buf` is too short.
+pub fn write_enum_be<E: EnumRepr>(value: E, buf: &mut [u8]) -> Result<usize, EndianError> {
+    value.to_repr().write_bytes(buf, EndiannessType::BigEndian)
+}
+
+/// write the discriminant of `value` in little endian to the front of `buf`
+///
+/// returns the number of bytes written.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `buf` is too short.
+pub fn write_enum_le<E: EnumRepr>(value: E, buf: &mut [u8]) -> Result<usize, EndianError> {
+    value
+        .to_repr()
+        .write_bytes(buf, EndiannessType::LittleEndian)
+}
+
+/// read a big endian discriminant from the front of `bytes`
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is too short and
+/// [`EndianError::InvalidDiscriminant`] if no variant matches.
+pub fn read_enum_be<E: EnumRepr>(bytes: &[u8]) -> Result<E, EndianError> {
+    let repr = E::Repr::from_bytes(bytes, EndiannessType::BigEndian)?;
+    E::from_repr(repr).ok_or(EndianError::InvalidDiscriminant)
+}
+
+/// read a little endian discriminant from the front of `bytes`
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is too short and
+/// [`EndianError::InvalidDiscriminant`] if no variant matches.
+pub fn read_enum_le<E: EnumRepr>(bytes: &[u8]) -> Result<E, EndianError> {
+    let repr = E::Repr::from_bytes(bytes, EndiannessType::LittleEndian)?;
+    E::from_repr(repr).ok_or(EndianError::InvalidDiscriminant)
+}
+
+/// read a big endian `u16` code and look it up in `table`
+///
+/// a data driven alternative to [`EnumRepr`] for sparse code spaces. the
+/// first entry whose code matches is returned.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 2 bytes
+/// and [`EndianError::InvalidDiscriminant`] if the code is not in `table`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_u16_be_mapped;
+///
+/// let table = [(0x0001, "ping"), (0x8000, "pong")];
+/// assert_eq!(read_u16_be_mapped(&[0x80, 0x00], &table), Ok("pong"));
+/// ```
+pub fn read_u16_be_mapped<T: Copy>(bytes: &[u8], table: &[(u16, T)]) -> Result<T, EndianError> {
+    let code = u16::from_bytes(bytes, EndiannessType::BigEndian)?;
+    table
+        .iter()
+        .find(|&&(key, _)| key == code)
+        .map(|&(_, value)| value)
+        .ok_or(EndianError::InvalidDiscriminant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[repr(u16)]
+    enum Command {
+        Reset = 0x0001,
+        Status = 0x0102,
+        Shutdown = 0xff00,
+    }
+
+    impl_enum_repr!(Command: u16 { Reset, Status, Shutdown });
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[repr(i8)]
+    enum Sign {
+        Negative = -1,
+        Positive = 1,
+    }
+
+    impl_enum_repr!(Sign: i8 { Negative, Positive });
+
+    #[test]
+    fn test_enum_round_trip_be() {
+        let mut buf = [0u8; 4];
+
+        assert_eq!(write_enum_be(Command::Status, &mut buf), Ok(2));
+        assert_eq!(buf[..2], [0x01, 0x02]);
+        assert_eq!(read_enum_be::<Command>(&buf), Ok(Command::Status));
+
+        write_enum_be(Command::Shutdown, &mut buf).unwrap();
+        assert_eq!(read_enum_be::<Command>(&buf), Ok(Command::Shutdown));
+    }
+
+    #[test]
+    fn test_enum_round_trip_le() {
+        let mut buf = [0u8; 2];
+
+        write_enum_le(Command::Status, &mut buf).unwrap();
+        assert_eq!(buf, [0x02, 0x01]);
+        assert_eq!(read_enum_le::<Command>(&buf), Ok(Command::Status));
+        assert_eq!(
+            read_enum_be::<Command>(&buf),
+            Err(EndianError::InvalidDiscriminant)
+        );
+
+        write_enum_le(Sign::Negative, &mut buf).unwrap();
+        assert_eq!(buf[0], 0xff);
+        assert_eq!(read_enum_le::<Sign>(&buf), Ok(Sign::Negative));
+    }
+
+    #[test]
+    fn test_enum_errors() {
+        assert_eq!(
+            read_enum_be::<Command>(&[0x00, 0x02]),
+            Err(EndianError::InvalidDiscriminant)
+        );
+        assert_eq!(
+            read_enum_be::<Command>(&[0x01]),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+        assert_eq!(
+            write_enum_be(Command::Reset, &mut [0u8; 1]),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Opcode {
+        Read,
+        Write,
+        Erase,
+    }
+
+    const OPCODES: [(u16, Opcode); 3] = [
+        (0x0003, Opcode::Read),
+        (0x0102, Opcode::Write),
+        (0xd800, Opcode::Erase),
+    ];
+
+    #[test]
+    fn test_read_u16_be_mapped() {
+        assert_eq!(
+            read_u16_be_mapped(&[0x00, 0x03], &OPCODES),
+            Ok(Opcode::Read)
+        );
+        assert_eq!(
+            read_u16_be_mapped(&[0x01, 0x02, 0xff], &OPCODES),
+            Ok(Opcode::Write)
+        );
+        assert_eq!(
+            read_u16_be_mapped(&[0xd8, 0x00], &OPCODES),
+            Ok(Opcode::Erase)
+        );
+    }
+
+    #[test]
+    fn test_read_u16_be_mapped_unknown() {
+        // the little endian reading of a known code is not accepted
+        assert_eq!(
+            read_u16_be_mapped(&[0x02, 0x01], &OPCODES),
+            Err(EndianError::InvalidDiscriminant)
+        );
+        assert_eq!(
+            read_u16_be_mapped::<Opcode>(&[0x00, 0x03], &[]),
+            Err(EndianError::InvalidDiscriminant)
+        );
+        assert_eq!(
+            read_u16_be_mapped(&[0x00], &OPCODES),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use core::fmt;
+
+/// error returned by the slice based conversion helpers, readers and writers
+///
+/// implements [`Display`](fmt::Display) in every configuration and
+/// `std::error::Error` when the `std` feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EndianError {
+    /// the buffer ended before the value could be read
+    UnexpectedEof {
+        /// number of bytes required
+        needed: usize,
+        /// number of bytes left in the buffer
+        available: usize,
+    },
+    /// the requested integer width is not supported
+    InvalidWidth(usize),
+    /// the buffer length is not a multiple of the integer width
+    MisalignedLength {
+        /// length of the buffer in bytes
+        len: usize,
+        /// integer width in bytes
+        width: usize,
+    },
+    /// an offset points outside of the buffer
+    OffsetOutOfBounds {
+        /// offending offset
+        offset: usize,
+        /// length of the buffer in bytes
+        len: usize,
+    },
+    /// a discriminant does not match any enum variant
+    InvalidDiscriminant,
+    /// a value does not fit in the target width
+    Overflow,
+    /// a row stride is shorter than the row it steps over
+    InvalidStride {
+        /// stride between row starts in bytes
+        stride: usize,
+        /// length of one row in bytes
+        row_len: usize,
+    },
+    /// a format version is outside of the supported range
+    UnsupportedVersion {
+        /// version read from the buffer
+        found: u16,
+        /// oldest supported version
+        min: u16,
+        /// newest supported version
+        max: u16,
+    },
+    /// a checksum did not match the data it covers
+    ChecksumMismatch {
+        /// checksum stored in the buffer
+        expected: u64,
+        /// checksum computed over the data
+        found: u64,
+    },
+    /// a nul terminated string has no terminator
+    MissingNul,
+    /// string bytes are not valid utf-8
+    InvalidUtf8,
+    /// a field did not hold its required value
+    UnexpectedValue {
+        /// offset of the field from the start of the buffer
+        offset: usize,
+        /// required value
+        expected: u64,
+        /// value actually read
+        found: u64,
+    },
+    /// a 32-bit value is not a unicode scalar value
+    InvalidChar(u32),
+    /// a value required to be nonzero was zero
+    ZeroValue,
+    /// bytes were left over after the last field
+    TrailingBytes(usize),
+    /// an unsupported bit count, such as the total width of a fixed-point format
+    InvalidBits(u32),
+    /// a length field does not match the length of the buffer it describes
+    LengthMismatch {
+        /// length stored in the length field
+        declared: u64,
+        /// length of the buffer
+        actual: u64,
+    },
+    /// a reserved field that must be zero was not
+    ReservedNonZero {
+        /// offset of the field from the start of the buffer
+        offset: usize,
+        /// value actually read
+        value: u32,
+    },
+}
+
+impl fmt::Display for EndianError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EndianError::UnexpectedEof { needed, available } => write!(
+                f,
+                "unexpected end of buffer: needed {} bytes but only {} available",
+                needed, available
+            ),
+            EndianError::InvalidWidth(width) => {
+                write!(f, "unsupported integer width of {} bytes", width)
+            }
+            EndianError::MisalignedLength { len, width } => write!(
+                f,
+                "buffer length {} is not a multiple of {} bytes",
+                len, width
+            ),
+            EndianError::OffsetOutOfBounds { offset, len } => write!(
+                f,
+                "offset {} is out of bounds for buffer of {} bytes",
+                offset, len
+            ),
+            EndianError::InvalidDiscriminant => f.write_str("invalid enum discriminant"),
+            EndianError::Overflow => f.write_str("value does not fit in the target width"),
+            EndianError::InvalidStride { stride, row_len } => write!(
+                f,
+                "stride of {} bytes is shorter than a row of {} bytes",
+                stride, row_len
+            ),
+            EndianError::UnsupportedVersion { found, min, max } => write!(
+                f,
+                "unsupported version {}, expected {} to {}",
+                found, min, max
+            ),
+            EndianError::ChecksumMismatch { expected, found } => write!(
+                f,
+                "checksum mismatch: stored {:#x} but computed {:#x}",
+                expected, found
+            ),
+            EndianError::MissingNul => f.write_str("string is not nul terminated"),
+            EndianError::InvalidUtf8 => f.write_str("string is not valid utf-8"),
+            EndianError::UnexpectedValue {
+                offset,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {:#x} at offset {} but found {:#x}",
+                expected, offset, found
+            ),
+            EndianError::InvalidChar(value) => {
+                write!(f, "{:#x} is not a unicode scalar value", value)
+            }
+            EndianError::ZeroValue => f.write_str("value required to be nonzero was zero"),
+            EndianError::TrailingBytes(len) => {
+                write!(f, "{} trailing bytes after the last field", len)
+            }
+            EndianError::InvalidBits(bits) => write!(f, "unsupported width of {} bits", bits),
+            EndianError::LengthMismatch { declared, actual } => write!(
+                f,
+                "length field declares {} bytes but the buffer holds {}",
+                declared, actual
+            ),
+            EndianError::ReservedNonZero { offset, value } => write!(
+                f,
+                "reserved field at offset {} is {:#x} instead of zero",
+                offset, value
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EndianError {}
+
+/// the previous name of [`EndianError`], kept so existing code keeps compiling
+pub type ReadError = EndianError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Write;
+
+    // render `error` into a fixed buffer, since the crate has no allocator by default
+    fn display(error: EndianError) -> Buffer {
+        let mut buf = Buffer {
+            bytes: [0; 128],
+            len: 0,
+        };
+        write!(buf, "{}", error).unwrap();
+        buf
+    }
+
+    struct Buffer {
+        bytes: [u8; 128],
+        len: usize,
+    }
+
+    impl Buffer {
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+        }
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_display_unexpected_eof() {
+        let error = EndianError::UnexpectedEof {
+            needed: 4,
+            available: 2,
+        };
+
+        assert_eq!(
+            display(error).as_str(),
+            "unexpected end of buffer: needed 4 bytes but only 2 available"
+        );
+    }
+
+    #[test]
+    fn test_display_details() {
+        assert_eq!(
+            display(EndianError::MisalignedLength { len: 7, width: 4 }).as_str(),
+            "buffer length 7 is not a multiple of 4 bytes"
+        );
+        assert_eq!(
+            display(EndianError::UnexpectedValue {
+                offset: 8,
+                expected: 0xdeadbeef,
+                found: 0
+            })
+            .as_str(),
+            "expected 0xdeadbeef at offset 8 but found 0x0"
+        );
+        assert_eq!(
+            display(EndianError::Overflow).as_str(),
+            "value does not fit in the target width"
+        );
+        assert_eq!(
+            display(EndianError::InvalidChar(0xd800)).as_str(),
+            "0xd800 is not a unicode scalar value"
+        );
+        assert_eq!(
+            display(EndianError::ZeroValue).as_str(),
+            "value required to be nonzero was zero"
+        );
+        assert_eq!(
+            display(EndianError::TrailingBytes(3)).as_str(),
+            "3 trailing bytes after the last field"
+        );
+        assert_eq!(
+            display(EndianError::ReservedNonZero {
+                offset: 4,
+                value: 0x80
+            })
+            .as_str(),
+            "reserved field at offset 4 is 0x80 instead of zero"
+        );
+        assert_eq!(
+            display(EndianError::LengthMismatch {
+                declared: 12,
+                actual: 10
+            })
+            .as_str(),
+            "length field declares 12 bytes but the buffer holds 10"
+        );
+        assert_eq!(
+            display(EndianError::InvalidBits(12)).as_str(),
+            "unsupported width of 12 bits"
+        );
+    }
+
+    #[test]
+    fn test_read_error_alias() {
+        let error: ReadError = EndianError::InvalidUtf8;
+        assert_eq!(error, EndianError::InvalidUtf8);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_std_error() {
+        extern crate std;
+        use std::string::ToString;
+
+        let error: std::boxed::Box<dyn std::error::Error> =
+            std::boxed::Box::new(EndianError::MissingNul);
+        assert_eq!(error.to_string(), "string is not nul terminated");
+    }
+}
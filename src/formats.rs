@@ -0,0 +1,281 @@
+//! helpers for concrete packed field formats
+
+use crate::{read_fixed_be, EndianError, EndianSerialize, EndiannessType};
+
+/// read an RGB565 pixel stored as a `u16` in `order` and expand it to 8-bit channels
+///
+/// red and blue hold 5 bits and green 6 bits. each channel is scaled to the
+/// full 8-bit range by replicating its high bits into the low bits, so `0` maps
+/// to `0` and the channel maximum maps to `255`.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `buf` is shorter than 2 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{read_rgb565, EndiannessType};
+///
+/// assert_eq!(read_rgb565(&[0xf8, 0x00], EndiannessType::BigEndian), Ok((255, 0, 0)));
+/// ```
+pub fn read_rgb565(buf: &[u8], order: EndiannessType) -> Result<(u8, u8, u8), EndianError> {
+    let pixel = u16::from_bytes(buf, order)?;
+
+    let r = ((pixel >> 11) & 0x1f) as u8;
+    let g = ((pixel >> 5) & 0x3f) as u8;
+    let b = (pixel & 0x1f) as u8;
+
+    Ok((
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ))
+}
+
+/// read three big endian `f32` values starting at `offset`
+///
+/// meant for interleaved vertex buffers, where `offset` is the vertex index
+/// times the stride plus the attribute offset.
+///
+/// # errors
+///
+/// returns [`EndianError::OffsetOutOfBounds`] if `offset` is past the end of
+/// `buf` and [`EndianError::UnexpectedEof`] if fewer than 12 bytes follow it.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_vec3_f32_be;
+///
+/// let position = [0x3f, 0x80, 0, 0, 0x40, 0, 0, 0, 0xc0, 0x40, 0, 0];
+/// assert_eq!(read_vec3_f32_be(&position, 0), Ok([1.0, 2.0, -3.0]));
+/// ```
+pub fn read_vec3_f32_be(buf: &[u8], offset: usize) -> Result<[f32; 3], EndianError> {
+    let bytes = buf.get(offset..).ok_or(EndianError::OffsetOutOfBounds {
+        offset,
+        len: buf.len(),
+    })?;
+
+    if bytes.len() < 12 {
+        return Err(EndianError::UnexpectedEof {
+            needed: 12,
+            available: bytes.len(),
+        });
+    }
+
+    let mut vec = [0.0; 3];
+    for (component, word) in vec.iter_mut().zip(bytes.chunks_exact(4)) {
+        *component = f32::from_bits(u32::from_bytes(word, EndiannessType::BigEndian)?);
+    }
+
+    Ok(vec)
+}
+
+/// read a packed fat/dos date stored as a `u16` in `order`
+///
+/// bits 9 to 15 hold the year since 1980, bits 5 to 8 the month and bits 0 to
+/// 4 the day. dos itself stores dates in little endian. returns
+/// `(year, month, day)`. the month and day are not range checked, since some
+/// tools write zero for an unset date.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `buf` is shorter than 2 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{read_dos_date, EndiannessType};
+///
+/// assert_eq!(read_dos_date(&[0x6f, 0x52], EndiannessType::LittleEndian), Ok((2021, 3, 15)));
+/// ```
+pub fn read_dos_date(buf: &[u8], order: EndiannessType) -> Result<(u16, u8, u8), EndianError> {
+    let date = u16::from_bytes(buf, order)?;
+
+    let year = 1980 + (date >> 9);
+    let month = ((date >> 5) & 0x0f) as u8;
+    let day = (date & 0x1f) as u8;
+    Ok((year, month, day))
+}
+
+/// read the offset and 2x2 transform of an opentype composite glyph component
+///
+/// decodes the 12 byte layout used when a component sets both
+/// `ARG_1_AND_2_ARE_WORDS` and `WE_HAVE_A_TWO_BY_TWO`: `dx` and `dy` as big
+/// endian `int16` font units, followed by `xscale`, `scale01`, `scale10` and
+/// `yscale` as big endian F2Dot14 values. returns the affine matrix
+/// `[xscale, scale01, scale10, yscale, dx, dy]`, which maps a point with
+/// `x' = xscale * x + scale10 * y + dx` and `y' = scale01 * x + yscale * y + dy`.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 12 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_font_matrix_be;
+///
+/// // identity transform shifted by (10, -1)
+/// let component = [0x00, 0x0a, 0xff, 0xff, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x00];
+/// assert_eq!(read_font_matrix_be(&component), Ok([1.0, 0.0, 0.0, 1.0, 10.0, -1.0]));
+/// ```
+pub fn read_font_matrix_be(bytes: &[u8]) -> Result<[f64; 6], EndianError> {
+    if bytes.len() < 12 {
+        return Err(EndianError::UnexpectedEof {
+            needed: 12,
+            available: bytes.len(),
+        });
+    }
+
+    let dx = f64::from(i16::from_bytes(bytes, EndiannessType::BigEndian)?);
+    let dy = f64::from(i16::from_bytes(&bytes[2..], EndiannessType::BigEndian)?);
+
+    let mut matrix = [0.0, 0.0, 0.0, 0.0, dx, dy];
+    for (value, f2dot14) in matrix.iter_mut().zip(bytes[4..12].chunks_exact(2)) {
+        *value = read_fixed_be(f2dot14, 2, 14)?;
+    }
+
+    Ok(matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_rgb565_big_endian() {
+        let order = EndiannessType::BigEndian;
+
+        assert_eq!(read_rgb565(&[0xf8, 0x00], order), Ok((255, 0, 0)));
+        assert_eq!(read_rgb565(&[0x07, 0xe0], order), Ok((0, 255, 0)));
+        assert_eq!(read_rgb565(&[0x00, 0x1f], order), Ok((0, 0, 255)));
+        assert_eq!(read_rgb565(&[0xff, 0xff], order), Ok((255, 255, 255)));
+        assert_eq!(read_rgb565(&[0x00, 0x00], order), Ok((0, 0, 0)));
+    }
+
+    #[test]
+    fn test_read_rgb565_little_endian() {
+        let order = EndiannessType::LittleEndian;
+
+        assert_eq!(read_rgb565(&[0x00, 0xf8], order), Ok((255, 0, 0)));
+        assert_eq!(read_rgb565(&[0xe0, 0x07], order), Ok((0, 255, 0)));
+        assert_eq!(read_rgb565(&[0x1f, 0x00], order), Ok((0, 0, 255)));
+    }
+
+    #[test]
+    fn test_read_rgb565_scaling() {
+        // mid-scale red 0b10000 expands to 0b10000100
+        assert_eq!(
+            read_rgb565(&[0x80, 0x00], EndiannessType::BigEndian),
+            Ok((0x84, 0, 0))
+        );
+        assert_eq!(
+            read_rgb565(&[0xf8], EndiannessType::BigEndian),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_vec3_f32_be_strided() {
+        // two vertices with a 16 byte stride: a u32 id followed by the position
+        let mut buf = [0u8; 32];
+        for (vertex, position) in [[1.0f32, 2.0, 3.0], [-0.5, 0.25, 1e6]].iter().enumerate() {
+            let base = vertex * 16;
+            buf[base..base + 4].copy_from_slice(&(vertex as u32).to_be_bytes());
+            for (i, component) in position.iter().enumerate() {
+                let at = base + 4 + i * 4;
+                buf[at..at + 4].copy_from_slice(&component.to_be_bytes());
+            }
+        }
+
+        assert_eq!(read_vec3_f32_be(&buf, 4), Ok([1.0, 2.0, 3.0]));
+        assert_eq!(read_vec3_f32_be(&buf, 16 + 4), Ok([-0.5, 0.25, 1e6]));
+    }
+
+    #[test]
+    fn test_read_vec3_f32_be_bounds() {
+        let buf = [0u8; 16];
+
+        assert_eq!(
+            read_vec3_f32_be(&buf, 8),
+            Err(EndianError::UnexpectedEof {
+                needed: 12,
+                available: 8
+            })
+        );
+        assert_eq!(
+            read_vec3_f32_be(&buf, 17),
+            Err(EndianError::OffsetOutOfBounds {
+                offset: 17,
+                len: 16
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_dos_date() {
+        // 2021-03-15 packs to 0x526f
+        let order = EndiannessType::LittleEndian;
+        assert_eq!(read_dos_date(&[0x6f, 0x52], order), Ok((2021, 3, 15)));
+        assert_eq!(
+            read_dos_date(&[0x52, 0x6f], EndiannessType::BigEndian),
+            Ok((2021, 3, 15))
+        );
+
+        // the range ends of the packed fields
+        assert_eq!(read_dos_date(&[0x21, 0x00], order), Ok((1980, 1, 1)));
+        assert_eq!(read_dos_date(&[0x9f, 0xff], order), Ok((2107, 12, 31)));
+        assert_eq!(
+            read_dos_date(&[0x6f], order),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_font_matrix_be() {
+        // a component mirrored horizontally, scaled by 0.5 vertically with a
+        // slight shear, and moved by (-300, 1200)
+        let component = [
+            0xfe, 0xd4, // dx = -300
+            0x04, 0xb0, // dy = 1200
+            0xc0, 0x00, // xscale = -1.0
+            0x00, 0x00, // scale01 = 0.0
+            0x10, 0x00, // scale10 = 0.25
+            0x20, 0x00, // yscale = 0.5
+        ];
+
+        assert_eq!(
+            read_font_matrix_be(&component),
+            Ok([-1.0, 0.0, 0.25, 0.5, -300.0, 1200.0])
+        );
+    }
+
+    #[test]
+    fn test_read_font_matrix_be_f2dot14_range() {
+        // F2Dot14 spans -2.0 to just under 2.0
+        let mut component = [0u8; 12];
+        component[4..6].copy_from_slice(&[0x80, 0x00]);
+        component[6..8].copy_from_slice(&[0x7f, 0xff]);
+        component[8..10].copy_from_slice(&[0xff, 0xff]);
+
+        let matrix = read_font_matrix_be(&component).unwrap();
+        assert_eq!(matrix[0], -2.0);
+        assert_eq!(matrix[1], 1.99993896484375);
+        assert_eq!(matrix[2], -0.00006103515625);
+        assert_eq!(
+            read_font_matrix_be(&component[..11]),
+            Err(EndianError::UnexpectedEof {
+                needed: 12,
+                available: 11
+            })
+        );
+    }
+}
@@ -0,0 +1,538 @@
+//! checksummed frame helpers
+
+use crate::{EndianError, EndianSerialize, EndiannessType};
+
+// lookup table for the reflected crc-32 polynomial 0xedb88320
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// compute the crc-32 (iso-hdlc, as used by ethernet, zip and png) of `bytes`
+///
+/// # examples
+///
+/// ```
+/// use endbyte::crc32;
+///
+/// assert_eq!(crc32(b"123456789"), 0xcbf43926);
+/// ```
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        CRC32_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// append a big endian crc-32 of the first `len` bytes of `buf` after them
+///
+/// returns the total frame length, `len + 4`.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `buf` is shorter than the whole
+/// frame of `len + 4` bytes, whether `len` is past the end of `buf` or only
+/// the trailer does not fit.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{append_crc32_be, verify_crc32_be};
+///
+/// let mut frame = [0x01, 0x02, 0, 0, 0, 0];
+/// let len = append_crc32_be(&mut frame, 2).unwrap();
+/// assert_eq!(verify_crc32_be(&frame[..len]), Ok(&[0x01, 0x02][..]));
+/// ```
+pub fn append_crc32_be(buf: &mut [u8], len: usize) -> Result<usize, EndianError> {
+    // report the whole frame, not just the trailer, when it does not fit
+    let needed = len.checked_add(4).filter(|&needed| needed <= buf.len());
+    let Some(needed) = needed else {
+        return Err(EndianError::UnexpectedEof {
+            needed: len.saturating_add(4),
+            available: buf.len(),
+        });
+    };
+
+    let (payload, trailer) = buf.split_at_mut(len);
+    crc32(payload).write_bytes(trailer, EndiannessType::BigEndian)?;
+    Ok(needed)
+}
+
+/// verify the big endian crc-32 trailer of `frame` and return the payload before it
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `frame` is shorter than the trailer
+/// and [`EndianError::ChecksumMismatch`] if the checksum does not match.
+pub fn verify_crc32_be(frame: &[u8]) -> Result<&[u8], EndianError> {
+    let len = frame
+        .len()
+        .checked_sub(4)
+        .ok_or(EndianError::UnexpectedEof {
+            needed: 4,
+            available: frame.len(),
+        })?;
+
+    let (payload, trailer) = frame.split_at(len);
+    let expected = u32::from_bytes(trailer, EndiannessType::BigEndian)?;
+    let found = crc32(payload);
+
+    if expected != found {
+        return Err(EndianError::ChecksumMismatch {
+            expected: expected.into(),
+            found: found.into(),
+        });
+    }
+
+    Ok(payload)
+}
+
+/// split `buf` into a decoded header of `header_len` bytes and the payload after it
+///
+/// `decode` receives exactly the header bytes, so it can decode the fields
+/// with any of the readers, or with [`EndianSerialize::from_bytes`] on a
+/// derived struct. the payload borrows the rest of `buf`.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `buf` is shorter than the header,
+/// and any error returned by `decode`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{split_header, EndianReader};
+///
+/// let frame = [0x00, 0x07, 0xaa, 0xbb];
+/// let (kind, payload) = split_header(&frame, 2, |header| {
+///     EndianReader::new(header).read_u16_be()
+/// })
+/// .unwrap();
+/// assert_eq!(kind, 7);
+/// assert_eq!(payload, [0xaa, 0xbb]);
+/// ```
+pub fn split_header<H>(
+    buf: &[u8],
+    header_len: usize,
+    decode: impl FnOnce(&[u8]) -> Result<H, EndianError>,
+) -> Result<(H, &[u8]), EndianError> {
+    if buf.len() < header_len {
+        return Err(EndianError::UnexpectedEof {
+            needed: header_len,
+            available: buf.len(),
+        });
+    }
+
+    let (header, payload) = buf.split_at(header_len);
+    Ok((decode(header)?, payload))
+}
+
+/// parser for the body of one version of a format, see [`parse_versioned_u16_be`]
+pub type VersionParser<T> = fn(&[u8]) -> Result<T, EndianError>;
+
+/// read a big endian `u16` version and hand the rest of `buf` to its parser
+///
+/// `parsers[0]` handles version 1, `parsers[1]` version 2 and so on. the
+/// chosen parser receives the bytes after the version field. adding a layout
+/// only means appending its parser to the table.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `buf` is shorter than 2 bytes,
+/// [`EndianError::UnsupportedVersion`] if no parser handles the version, and
+/// any error returned by the parser.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{parse_versioned_u16_be, EndianError, EndianSerialize, EndiannessType};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Message {
+///     id: u32,
+///     flags: u8,
+/// }
+///
+/// // version 1 had a 16-bit id and no flags
+/// fn parse_v1(body: &[u8]) -> Result<Message, EndianError> {
+///     let id = u16::from_bytes(body, EndiannessType::BigEndian)?;
+///     Ok(Message { id: id.into(), flags: 0 })
+/// }
+///
+/// fn parse_v2(body: &[u8]) -> Result<Message, EndianError> {
+///     let id = u32::from_bytes(body, EndiannessType::BigEndian)?;
+///     let flags = u8::from_bytes(&body[4..], EndiannessType::BigEndian)?;
+///     Ok(Message { id, flags })
+/// }
+///
+/// let parse = |buf: &[u8]| parse_versioned_u16_be(buf, &[parse_v1, parse_v2]);
+/// assert_eq!(parse(&[0, 1, 0, 7]), Ok(Message { id: 7, flags: 0 }));
+/// assert_eq!(parse(&[0, 2, 0, 0, 0, 7, 1]), Ok(Message { id: 7, flags: 1 }));
+/// ```
+pub fn parse_versioned_u16_be<T>(
+    buf: &[u8],
+    parsers: &[VersionParser<T>],
+) -> Result<T, EndianError> {
+    let version = u16::from_bytes(buf, EndiannessType::BigEndian)?;
+
+    let parse = usize::from(version)
+        .checked_sub(1)
+        .and_then(|index| parsers.get(index))
+        .ok_or(EndianError::UnsupportedVersion {
+            found: version,
+            min: 1,
+            max: u16::try_from(parsers.len()).unwrap_or(u16::MAX),
+        })?;
+    parse(&buf[2..])
+}
+
+/// check that a length prefixed, crc-32 terminated frame is self-consistent
+///
+/// the frame starts with a big endian `u32` holding the total frame length,
+/// including the prefix and the trailer, and ends with a big endian crc-32 of
+/// every byte before the trailer.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `frame` is shorter than the prefix
+/// and trailer, [`EndianError::LengthMismatch`] if the length field does not
+/// match `frame.len()` and [`EndianError::ChecksumMismatch`] if the checksum
+/// does not match, so the two failures can be told apart.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{append_crc32_be, validate_frame};
+///
+/// let mut frame = [0x00, 0x00, 0x00, 0x0a, 0xab, 0xcd, 0, 0, 0, 0];
+/// append_crc32_be(&mut frame, 6).unwrap();
+/// assert_eq!(validate_frame(&frame), Ok(()));
+/// ```
+pub fn validate_frame(frame: &[u8]) -> Result<(), EndianError> {
+    if frame.len() < 8 {
+        return Err(EndianError::UnexpectedEof {
+            needed: 8,
+            available: frame.len(),
+        });
+    }
+
+    let len = u32::from_bytes(frame, EndiannessType::BigEndian)?;
+    if usize::try_from(len) != Ok(frame.len()) {
+        return Err(EndianError::LengthMismatch {
+            declared: len.into(),
+            actual: frame.len() as u64,
+        });
+    }
+
+    verify_crc32_be(frame)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_check_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(
+            crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414fa339
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Reading {
+        sensor: u16,
+        value: i32,
+    }
+
+    impl Reading {
+        fn write(&self, buf: &mut [u8]) -> Result<usize, EndianError> {
+            let order = EndiannessType::BigEndian;
+            let len = self.sensor.write_bytes(buf, order)?;
+            let len = len + self.value.write_bytes(&mut buf[len..], order)?;
+            append_crc32_be(buf, len)
+        }
+
+        fn read(frame: &[u8]) -> Result<Self, EndianError> {
+            let payload = verify_crc32_be(frame)?;
+            let order = EndiannessType::BigEndian;
+            Ok(Self {
+                sensor: u16::from_bytes(payload, order)?,
+                value: i32::from_bytes(&payload[2..], order)?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_crc32_frame_round_trip() {
+        let reading = Reading {
+            sensor: 7,
+            value: -1200,
+        };
+
+        let mut buf = [0u8; 16];
+        let len = reading.write(&mut buf).unwrap();
+        assert_eq!(len, 10);
+        assert_eq!(buf[..6], [0x00, 0x07, 0xff, 0xff, 0xfb, 0x50]);
+        assert_eq!(buf[6..10], crc32(&buf[..6]).to_be_bytes());
+
+        assert_eq!(Reading::read(&buf[..len]), Ok(reading));
+    }
+
+    #[test]
+    fn test_crc32_frame_errors() {
+        let mut buf = [0x01, 0x02, 0x03, 0, 0, 0, 0];
+        let len = append_crc32_be(&mut buf, 3).unwrap();
+
+        buf[1] ^= 0x10;
+        assert!(matches!(
+            verify_crc32_be(&buf[..len]),
+            Err(EndianError::ChecksumMismatch { .. })
+        ));
+
+        assert_eq!(
+            verify_crc32_be(&[0x01, 0x02]),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 2
+            })
+        );
+
+        // a short trailer reports the whole frame against the whole buffer
+        assert_eq!(
+            append_crc32_be(&mut buf, 5),
+            Err(EndianError::UnexpectedEof {
+                needed: 9,
+                available: 7
+            })
+        );
+
+        // and so does a payload length past the end of the buffer
+        assert_eq!(
+            append_crc32_be(&mut buf, 8),
+            Err(EndianError::UnexpectedEof {
+                needed: 12,
+                available: 7
+            })
+        );
+        assert_eq!(
+            append_crc32_be(&mut buf, usize::MAX),
+            Err(EndianError::UnexpectedEof {
+                needed: usize::MAX,
+                available: 7
+            })
+        );
+
+        // a trailer that exactly fills the buffer is fine
+        assert_eq!(append_crc32_be(&mut buf, 3), Ok(7));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Header {
+        kind: u16,
+        flags: u16,
+        len: u32,
+    }
+
+    fn decode_header(header: &[u8]) -> Result<Header, EndianError> {
+        let mut reader = crate::EndianReader::new(header);
+        Ok(Header {
+            kind: reader.read_u16_be()?,
+            flags: reader.read_u16_be()?,
+            len: reader.read_u32_be()?,
+        })
+    }
+
+    #[test]
+    fn test_split_header() {
+        let frame = [
+            0x00, 0x01, 0x80, 0x00, 0x00, 0x00, 0x00, 0x03, // header
+            0xaa, 0xbb, 0xcc, // payload
+        ];
+
+        let (header, payload) = split_header(&frame, 8, decode_header).unwrap();
+        assert_eq!(
+            header,
+            Header {
+                kind: 1,
+                flags: 0x8000,
+                len: 3
+            }
+        );
+        assert_eq!(payload, [0xaa, 0xbb, 0xcc]);
+        assert_eq!(payload.len(), header.len as usize);
+
+        // a frame holding only the header has an empty payload
+        let (_, payload) = split_header(&frame[..8], 8, decode_header).unwrap();
+        assert!(payload.is_empty());
+    }
+
+    #[test]
+    fn test_split_header_errors() {
+        let frame = [0x00, 0x01, 0x80, 0x00, 0x00];
+
+        assert_eq!(
+            split_header(&frame, 8, decode_header),
+            Err(EndianError::UnexpectedEof {
+                needed: 8,
+                available: 5
+            })
+        );
+
+        // errors from the decoder are passed through
+        assert_eq!(
+            split_header(&frame, 2, |_| Err::<(), _>(
+                EndianError::InvalidDiscriminant
+            )),
+            Err(EndianError::InvalidDiscriminant)
+        );
+    }
+
+    // build a frame around `payload` in `buf` and return its length
+    fn build_frame(buf: &mut [u8], payload: &[u8]) -> usize {
+        let len = 4 + payload.len();
+        ((len + 4) as u32)
+            .write_bytes(buf, EndiannessType::BigEndian)
+            .unwrap();
+        buf[4..len].copy_from_slice(payload);
+        append_crc32_be(buf, len).unwrap()
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Message {
+        V1 { id: u16 },
+        V2 { id: u32, flags: u8 },
+    }
+
+    fn parse_v1(body: &[u8]) -> Result<Message, EndianError> {
+        let id = u16::from_bytes(body, EndiannessType::BigEndian)?;
+        Ok(Message::V1 { id })
+    }
+
+    fn parse_v2(body: &[u8]) -> Result<Message, EndianError> {
+        let id = u32::from_bytes(body, EndiannessType::BigEndian)?;
+        let flags = u8::from_bytes(&body[4..], EndiannessType::BigEndian)?;
+        Ok(Message::V2 { id, flags })
+    }
+
+    fn parse(buf: &[u8]) -> Result<Message, EndianError> {
+        parse_versioned_u16_be(buf, &[parse_v1, parse_v2])
+    }
+
+    #[test]
+    fn test_parse_versioned() {
+        assert_eq!(
+            parse(&[0x00, 0x01, 0x12, 0x34]),
+            Ok(Message::V1 { id: 0x1234 })
+        );
+        assert_eq!(
+            parse(&[0x00, 0x02, 0x00, 0x01, 0x02, 0x03, 0x80]),
+            Ok(Message::V2 {
+                id: 0x00010203,
+                flags: 0x80
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_versioned_errors() {
+        for version in [0x0000, 0x0003, 0xffff] {
+            let buf = u16::to_be_bytes(version);
+            assert_eq!(
+                parse(&buf),
+                Err(EndianError::UnsupportedVersion {
+                    found: version,
+                    min: 1,
+                    max: 2
+                })
+            );
+        }
+
+        assert_eq!(
+            parse(&[0x00]),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+        // errors from the chosen parser are passed through
+        assert_eq!(
+            parse(&[0x00, 0x02, 0x00, 0x01]),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_frame() {
+        let mut buf = [0u8; 16];
+
+        let len = build_frame(&mut buf, &[0x01, 0x02, 0x03]);
+        assert_eq!(len, 11);
+        assert_eq!(validate_frame(&buf[..len]), Ok(()));
+
+        // an empty payload is still a valid frame
+        let len = build_frame(&mut buf, &[]);
+        assert_eq!(validate_frame(&buf[..len]), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_frame_wrong_length() {
+        let mut buf = [0u8; 16];
+        let len = build_frame(&mut buf, &[0x01, 0x02, 0x03]);
+
+        // a frame cut short or with trailing garbage no longer matches its prefix
+        assert_eq!(
+            validate_frame(&buf[..len - 1]),
+            Err(EndianError::LengthMismatch {
+                declared: 11,
+                actual: 10
+            })
+        );
+        assert_eq!(
+            validate_frame(&buf[..len + 1]),
+            Err(EndianError::LengthMismatch {
+                declared: 11,
+                actual: 12
+            })
+        );
+        assert_eq!(
+            validate_frame(&buf[..7]),
+            Err(EndianError::UnexpectedEof {
+                needed: 8,
+                available: 7
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_frame_bad_checksum() {
+        let mut buf = [0u8; 16];
+        let len = build_frame(&mut buf, &[0x01, 0x02, 0x03]);
+
+        buf[5] ^= 0x01;
+        assert!(matches!(
+            validate_frame(&buf[..len]),
+            Err(EndianError::ChecksumMismatch { .. })
+        ));
+    }
+}
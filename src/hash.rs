@@ -0,0 +1,162 @@
+//! host independent hashing of big endian serialized values
+
+use core::hash::Hasher;
+
+use crate::Endianness;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// fnv-1a 64-bit hasher that feeds every integer in big endian byte order
+///
+/// the default [`Hasher`] integer methods hash native byte order, so the same
+/// value hashes differently on big and little endian hosts. this hasher
+/// serializes integers passed to the `write_*` integer methods in big endian
+/// first, and hashes `usize`/`isize` as 64-bit values, so a `#[derive(Hash)]`
+/// type whose fields are all scalar integers hashes the same on every host.
+///
+/// it cannot fix values that hash themselves through [`Hasher::write`]: core
+/// hashes slices, arrays and vectors of integers by passing their native
+/// bytes to `write` in one call, which this hasher feeds in unchanged. hash
+/// such types through the `canonical_hash` method that `#[derive(Endianness)]`
+/// emits with the `canonical-hash` feature, which hashes the big endian
+/// serialized form instead.
+///
+/// # examples
+///
+/// ```
+/// use core::hash::{Hash, Hasher};
+/// use endbyte::CanonicalHasher;
+///
+/// #[derive(Hash)]
+/// struct Header {
+///     kind: u16,
+///     length: u32,
+/// }
+///
+/// let mut hasher = CanonicalHasher::new();
+/// Header { kind: 1, length: 2 }.hash(&mut hasher);
+///
+/// // identical to hashing the big endian serialized fields directly
+/// assert_eq!(hasher.finish(), CanonicalHasher::hash_bytes(&[0, 1, 0, 0, 0, 2]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CanonicalHasher {
+    state: u64,
+}
+
+impl CanonicalHasher {
+    /// create a hasher in its initial state
+    pub const fn new() -> Self {
+        Self {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+
+    /// hash already serialized bytes in one call
+    pub fn hash_bytes(bytes: &[u8]) -> u64 {
+        let mut hasher = Self::new();
+        hasher.write(bytes);
+        hasher.finish()
+    }
+}
+
+impl Default for CanonicalHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+macro_rules! write_big_endian {
+    ($($method:ident: $t:ty),*) => {
+        $(
+            #[inline]
+            fn $method(&mut self, value: $t) {
+                self.write(&value.host_to_big_endian().to_ne_bytes());
+            }
+        )*
+    };
+}
+
+impl Hasher for CanonicalHasher {
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state = (self.state ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    write_big_endian!(
+        write_u16: u16,
+        write_u32: u32,
+        write_u64: u64,
+        write_u128: u128,
+        write_i16: i16,
+        write_i32: i32,
+        write_i64: i64,
+        write_i128: i128
+    );
+
+    #[inline]
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, value: isize) {
+        self.write_i64(value as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::hash::Hash;
+
+    #[test]
+    fn test_fnv1a_vectors() {
+        assert_eq!(CanonicalHasher::hash_bytes(b""), 0xcbf29ce484222325);
+        assert_eq!(CanonicalHasher::hash_bytes(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(CanonicalHasher::hash_bytes(b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[derive(Hash)]
+    struct Record {
+        id: u32,
+        flags: u16,
+        offset: i64,
+    }
+
+    #[test]
+    fn test_canonical_hash_of_struct() {
+        let record = Record {
+            id: 0x01020304,
+            flags: 0x0506,
+            offset: -1,
+        };
+
+        let mut hasher = CanonicalHasher::new();
+        record.hash(&mut hasher);
+
+        let serialized = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        ];
+        assert_eq!(hasher.finish(), CanonicalHasher::hash_bytes(&serialized));
+
+        // precomputed so that a change in byte order is caught on any host
+        assert_eq!(hasher.finish(), 0x1cd35c7baad66202);
+    }
+
+    #[test]
+    fn test_canonical_hash_usize_is_64_bit() {
+        let mut hasher = CanonicalHasher::new();
+        hasher.write_usize(7);
+        assert_eq!(
+            hasher.finish(),
+            CanonicalHasher::hash_bytes(&7u64.to_be_bytes())
+        );
+    }
+}
@@ -0,0 +1,503 @@
+//! lazy iterators decoding integer streams
+
+use crate::{EndianError, EndianSerialize, Endianness, EndiannessType};
+
+// decode a chunk that is known to be four bytes long
+#[inline]
+fn u32_be(chunk: &[u8]) -> u32 {
+    u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]).big_endian_to_host()
+}
+
+/// lazily expand a run-length encoded stream of big endian `u32` values
+///
+/// the stream is a sequence of `(count, value)` pairs, both stored as big endian
+/// `u32`. each pair yields `value` repeated `count` times.
+///
+/// # errors
+///
+/// returns [`EndianError::MisalignedLength`] if `bytes` does not hold a whole
+/// number of 8 byte pairs.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_rle_u32_be;
+///
+/// let stream = [0, 0, 0, 2, 0, 0, 0, 7];
+/// assert!(read_rle_u32_be(&stream).unwrap().eq([7, 7]));
+/// ```
+pub fn read_rle_u32_be(bytes: &[u8]) -> Result<impl Iterator<Item = u32> + '_, EndianError> {
+    if bytes.len() % 8 != 0 {
+        return Err(EndianError::MisalignedLength {
+            len: bytes.len(),
+            width: 8,
+        });
+    }
+
+    Ok(bytes.chunks_exact(8).flat_map(|pair| {
+        let count = u32_be(&pair[..4]);
+        let value = u32_be(&pair[4..]);
+        core::iter::repeat_n(value, count as usize)
+    }))
+}
+
+/// lazily reconstruct values from a stream of big endian `u32` deltas
+///
+/// each delta is added to the running value, starting from `base`, and the new
+/// running value is yielded. the base itself is not yielded, so the iterator
+/// produces one value per delta. the addition wraps on overflow.
+///
+/// # errors
+///
+/// returns [`EndianError::MisalignedLength`] if `bytes` does not hold a whole
+/// number of 4 byte deltas.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_delta_u32_be;
+///
+/// let deltas = [0, 0, 0, 1, 0, 0, 0, 2];
+/// assert!(read_delta_u32_be(&deltas, 10).unwrap().eq([11, 13]));
+/// ```
+pub fn read_delta_u32_be(
+    bytes: &[u8],
+    base: u32,
+) -> Result<impl Iterator<Item = u32> + '_, EndianError> {
+    if bytes.len() % 4 != 0 {
+        return Err(EndianError::MisalignedLength {
+            len: bytes.len(),
+            width: 4,
+        });
+    }
+
+    Ok(bytes.chunks_exact(4).scan(base, |value, delta| {
+        *value = value.wrapping_add(u32_be(delta));
+        Some(*value)
+    }))
+}
+
+/// lazily decode interleaved big endian `i16` stereo frames
+///
+/// each 4 byte frame holds the left sample followed by the right sample, and
+/// is yielded as `(left, right)`.
+///
+/// # errors
+///
+/// returns [`EndianError::MisalignedLength`] if `bytes` ends with a partial frame.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_stereo_i16_be;
+///
+/// let frames = [0x00, 0x01, 0xff, 0xff];
+/// assert!(read_stereo_i16_be(&frames).unwrap().eq([(1, -1)]));
+/// ```
+pub fn read_stereo_i16_be(
+    bytes: &[u8],
+) -> Result<impl Iterator<Item = (i16, i16)> + '_, EndianError> {
+    if bytes.len() % 4 != 0 {
+        return Err(EndianError::MisalignedLength {
+            len: bytes.len(),
+            width: 4,
+        });
+    }
+
+    Ok(bytes.chunks_exact(4).map(|frame| {
+        let left = i16::from_ne_bytes([frame[0], frame[1]]).big_endian_to_host();
+        let right = i16::from_ne_bytes([frame[2], frame[3]]).big_endian_to_host();
+        (left, right)
+    }))
+}
+
+/// lazily decode big endian `u32` words together with their byte offsets
+///
+/// yields `(offset, value)` for each whole word, which is handy for printing
+/// annotated dumps. trailing bytes that do not make up a whole word are not
+/// yielded.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::enumerate_u32_be;
+///
+/// let words = [0, 0, 0, 1, 0, 0, 0, 2];
+/// assert!(enumerate_u32_be(&words).eq([(0, 1), (4, 2)]));
+/// ```
+pub fn enumerate_u32_be(bytes: &[u8]) -> impl Iterator<Item = (usize, u32)> + '_ {
+    bytes
+        .chunks_exact(4)
+        .enumerate()
+        .map(|(index, word)| (index * 4, u32_be(word)))
+}
+
+/// iterator over type-length-value entries padded to 4 bytes
+///
+/// each entry is a `u16` type, a `u16` value length and the value, followed
+/// by zero to three pad bytes up to the next multiple of 4. the type and
+/// length are big endian for [`new`](TlvReader::new) and in the chosen order
+/// for [`with_order`](TlvReader::with_order). the value is opaque and never
+/// converted. yields `(type, value)`, with the padding stripped. the padding
+/// of the last entry may be left out.
+///
+/// a truncated entry yields a [`EndianError::UnexpectedEof`] and ends the
+/// iteration.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::TlvReader;
+///
+/// let tlv = [0x00, 0x01, 0x00, 0x02, 0xaa, 0xbb, 0x00, 0x00];
+/// let mut entries = TlvReader::new(&tlv);
+/// assert_eq!(entries.next(), Some(Ok((1, &[0xaa, 0xbb][..]))));
+/// assert_eq!(entries.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TlvReader<'a> {
+    bytes: &'a [u8],
+    order: EndiannessType,
+}
+
+impl<'a> TlvReader<'a> {
+    /// create a reader over the big endian entries in `bytes`
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self::with_order(bytes, EndiannessType::BigEndian)
+    }
+
+    /// create a reader over entries in `bytes` whose headers are in `order`
+    pub const fn with_order(bytes: &'a [u8], order: EndiannessType) -> Self {
+        Self { bytes, order }
+    }
+
+    /// bytes that have not been read yet
+    pub const fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    fn read_entry(&mut self) -> Result<(u16, &'a [u8]), EndianError> {
+        let header = self.bytes.get(..4).ok_or(EndianError::UnexpectedEof {
+            needed: 4,
+            available: self.bytes.len(),
+        })?;
+        let kind = u16::from_bytes(header, self.order)?;
+        let len = u16::from_bytes(&header[2..], self.order)? as usize;
+
+        let value = self
+            .bytes
+            .get(4..4 + len)
+            .ok_or(EndianError::UnexpectedEof {
+                needed: 4 + len,
+                available: self.bytes.len(),
+            })?;
+
+        // the header is 4 bytes, so padding the value aligns the next entry
+        let next = (4 + len.next_multiple_of(4)).min(self.bytes.len());
+        self.bytes = &self.bytes[next..];
+        Ok((kind, value))
+    }
+}
+
+impl<'a> Iterator for TlvReader<'a> {
+    type Item = Result<(u16, &'a [u8]), EndianError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let entry = self.read_entry();
+        if entry.is_err() {
+            // stop after reporting a truncated entry
+            self.bytes = &[];
+        }
+        Some(entry)
+    }
+}
+
+/// iterator adapter applying a byte order conversion to every item
+///
+/// created by the methods of [`EndianIterExt`].
+#[derive(Clone)]
+pub struct MapEndian<I: Iterator> {
+    iter: I,
+    convert: fn(I::Item) -> I::Item,
+}
+
+impl<I: Iterator> Iterator for MapEndian<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(self.convert)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for MapEndian<I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(self.convert)
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for MapEndian<I> {}
+
+impl<I: Iterator + core::fmt::Debug> core::fmt::Debug for MapEndian<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MapEndian")
+            .field("iter", &self.iter)
+            .finish()
+    }
+}
+
+/// lazy byte order conversions for iterators over [`Endianness`] values
+///
+/// each method wraps the iterator in a [`MapEndian`] adapter that converts
+/// items as they are yielded, so no intermediate collection is needed.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::EndianIterExt;
+///
+/// let stored = [u16::from_ne_bytes([0x12, 0x34]), u16::from_ne_bytes([0x00, 0x01])];
+/// assert!(stored.into_iter().map_big_endian_to_host().eq([0x1234, 0x0001]));
+/// ```
+pub trait EndianIterExt: Iterator + Sized
+where
+    Self::Item: Endianness,
+{
+    /// convert every item from host byte order to big endian
+    fn map_host_to_big_endian(self) -> MapEndian<Self> {
+        MapEndian {
+            iter: self,
+            convert: Self::Item::host_to_big_endian,
+        }
+    }
+
+    /// convert every item from host byte order to little endian
+    fn map_host_to_little_endian(self) -> MapEndian<Self> {
+        MapEndian {
+            iter: self,
+            convert: Self::Item::host_to_little_endian,
+        }
+    }
+
+    /// convert every item from big endian to host byte order
+    fn map_big_endian_to_host(self) -> MapEndian<Self> {
+        MapEndian {
+            iter: self,
+            convert: Self::Item::big_endian_to_host,
+        }
+    }
+
+    /// convert every item from little endian to host byte order
+    fn map_little_endian_to_host(self) -> MapEndian<Self> {
+        MapEndian {
+            iter: self,
+            convert: Self::Item::little_endian_to_host,
+        }
+    }
+}
+
+impl<I: Iterator> EndianIterExt for I where I::Item: Endianness {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_rle_u32_be() {
+        let stream = [
+            0x00, 0x00, 0x00, 0x03, 0x12, 0x34, 0x56, 0x78, // 3 x 0x12345678
+            0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, // 2 x 1
+        ];
+
+        let mut values = read_rle_u32_be(&stream).unwrap();
+        for expected in [0x12345678, 0x12345678, 0x12345678, 1, 1] {
+            assert_eq!(values.next(), Some(expected));
+        }
+        assert_eq!(values.next(), None);
+    }
+
+    #[test]
+    fn test_read_rle_u32_be_zero_count() {
+        let stream = [0, 0, 0, 0, 0, 0, 0, 9, 0, 0, 0, 1, 0, 0, 0, 5];
+        assert!(read_rle_u32_be(&stream).unwrap().eq([5]));
+        assert_eq!(read_rle_u32_be(&[]).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_read_rle_u32_be_truncated() {
+        assert!(matches!(
+            read_rle_u32_be(&[0, 0, 0, 1, 0, 0]),
+            Err(EndianError::MisalignedLength { len: 6, width: 8 })
+        ));
+    }
+
+    #[test]
+    fn test_read_delta_u32_be() {
+        let deltas = [
+            0x00, 0x00, 0x00, 0x05, // +5
+            0x00, 0x00, 0x01, 0x00, // +256
+            0x00, 0x00, 0x00, 0x00, // +0
+        ];
+
+        assert!(read_delta_u32_be(&deltas, 1000)
+            .unwrap()
+            .eq([1005, 1261, 1261]));
+    }
+
+    #[test]
+    fn test_read_delta_u32_be_wraps() {
+        // adding u32::MAX is the same as subtracting one
+        let deltas = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+        assert!(read_delta_u32_be(&deltas, 1).unwrap().eq([0, u32::MAX]));
+        assert!(matches!(
+            read_delta_u32_be(&deltas[..5], 0),
+            Err(EndianError::MisalignedLength { len: 5, width: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_read_stereo_i16_be() {
+        let frames = [
+            0x12, 0x34, 0xed, 0xcc, // 0x1234, -0x1234
+            0x80, 0x00, 0x7f, 0xff, // i16::MIN, i16::MAX
+        ];
+
+        let mut samples = read_stereo_i16_be(&frames).unwrap();
+        assert_eq!(samples.next(), Some((0x1234, -0x1234)));
+        assert_eq!(samples.next(), Some((i16::MIN, i16::MAX)));
+        assert_eq!(samples.next(), None);
+    }
+
+    #[test]
+    fn test_read_stereo_i16_be_partial_frame() {
+        assert!(matches!(
+            read_stereo_i16_be(&[0x00, 0x01, 0x00, 0x02, 0x00, 0x03]),
+            Err(EndianError::MisalignedLength { len: 6, width: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_enumerate_u32_be_offsets() {
+        let words = [
+            0x00, 0x00, 0x00, 0x0a, //
+            0xde, 0xad, 0xbe, 0xef, //
+            0xff, 0xff, 0xff, 0xff, //
+        ];
+
+        let mut dump = enumerate_u32_be(&words);
+        assert_eq!(dump.next(), Some((0, 10)));
+        assert_eq!(dump.next(), Some((4, 0xdeadbeef)));
+        assert_eq!(dump.next(), Some((8, u32::MAX)));
+        assert_eq!(dump.next(), None);
+
+        // a partial trailing word is skipped
+        assert_eq!(enumerate_u32_be(&words[..7]).count(), 1);
+    }
+
+    #[test]
+    fn test_tlv_reader_padding() {
+        let tlv = [
+            0x00, 0x01, 0x00, 0x03, 0xaa, 0xbb, 0xcc, 0x00, // 3 byte value, 1 pad byte
+            0x80, 0x02, 0x00, 0x04, 0x01, 0x02, 0x03, 0x04, // 4 byte value, no padding
+            0x00, 0x03, 0x00, 0x00, // empty value
+            0x00, 0x04, 0x00, 0x01, 0xff, // last entry without its padding
+        ];
+
+        let mut entries = TlvReader::new(&tlv);
+        assert_eq!(entries.next(), Some(Ok((1, &[0xaa, 0xbb, 0xcc][..]))));
+        assert_eq!(entries.next(), Some(Ok((0x8002, &[1, 2, 3, 4][..]))));
+        assert_eq!(entries.next(), Some(Ok((3, &[][..]))));
+        assert_eq!(entries.next(), Some(Ok((4, &[0xff][..]))));
+        assert_eq!(entries.next(), None);
+    }
+
+    #[test]
+    fn test_tlv_reader_little_endian() {
+        let tlv = [
+            0x01, 0x00, 0x03, 0x00, 0x01, 0x02, 0x03, 0x00, // type 1, 3 value bytes
+            0x02, 0x00, 0x00, 0x00, // type 2, empty
+        ];
+
+        let mut entries = TlvReader::with_order(&tlv, EndiannessType::LittleEndian);
+        assert_eq!(entries.next(), Some(Ok((1, &[0x01, 0x02, 0x03][..]))));
+        assert_eq!(entries.next(), Some(Ok((2, &[][..]))));
+        assert_eq!(entries.next(), None);
+
+        // read as big endian the first entry claims 0x0300 value bytes
+        let mut entries = TlvReader::new(&tlv);
+        assert_eq!(
+            entries.next(),
+            Some(Err(EndianError::UnexpectedEof {
+                needed: 4 + 0x0300,
+                available: 12
+            }))
+        );
+    }
+
+    #[test]
+    fn test_tlv_reader_truncated() {
+        // the second entry claims 8 bytes but only 2 follow
+        let tlv = [
+            0x00, 0x01, 0x00, 0x01, 0x42, 0x00, 0x00, 0x00, //
+            0x00, 0x02, 0x00, 0x08, 0x01, 0x02,
+        ];
+
+        let mut entries = TlvReader::new(&tlv);
+        assert_eq!(entries.next(), Some(Ok((1, &[0x42][..]))));
+        assert_eq!(
+            entries.next(),
+            Some(Err(EndianError::UnexpectedEof {
+                needed: 12,
+                available: 6
+            }))
+        );
+        assert_eq!(entries.next(), None);
+
+        let mut header_only = TlvReader::new(&[0x00, 0x01]);
+        assert!(matches!(header_only.next(), Some(Err(_))));
+        assert_eq!(header_only.remaining(), []);
+    }
+
+    #[test]
+    fn test_endian_iter_ext() {
+        let host = [0x1234u16, 0x0001, 0xff00];
+
+        let big = host.into_iter().map_host_to_big_endian();
+        assert_eq!(big.len(), 3);
+        for (converted, value) in big.zip(host) {
+            assert_eq!(converted.to_ne_bytes(), value.to_be_bytes());
+        }
+        for (converted, value) in host.into_iter().map_host_to_little_endian().zip(host) {
+            assert_eq!(converted.to_ne_bytes(), value.to_le_bytes());
+        }
+
+        let stored = host.map(|value| u16::from_ne_bytes(value.to_be_bytes()));
+        assert!(stored.into_iter().map_big_endian_to_host().eq(host));
+        let stored = host.map(|value| u16::from_ne_bytes(value.to_le_bytes()));
+        assert!(stored.into_iter().map_little_endian_to_host().eq(host));
+    }
+
+    #[test]
+    fn test_endian_iter_ext_chains() {
+        let host = [1u16, 2, 3];
+
+        // the conversions compose with other adapters and run in reverse
+        let round_trip = host
+            .iter()
+            .copied()
+            .map_host_to_big_endian()
+            .map_big_endian_to_host()
+            .rev();
+        assert!(round_trip.eq([3, 2, 1]));
+    }
+}
@@ -1,8 +1,88 @@
 #![no_std]
 #![doc = include_str!("../readme.md")]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// lets the derive output name `::endbyte` inside this crate's own tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as endbyte;
+
+mod bitset;
+mod buffer;
+mod bytes;
+mod constant;
+mod enums;
+mod error;
+mod formats;
+mod frame;
+mod hash;
+mod iter;
+mod net;
+mod order;
+mod pdp;
+mod read;
+mod reader;
+mod register;
+mod repr;
+mod slice;
+mod swap;
+mod typed;
+mod write;
+mod writer;
+
+pub use bitset::*;
+pub use buffer::*;
+pub use bytes::*;
+pub use constant::*;
+pub use enums::*;
+pub use error::*;
+pub use formats::*;
+pub use frame::*;
+pub use hash::*;
+pub use iter::*;
+pub use net::*;
+pub use order::*;
+pub use pdp::*;
+pub use read::*;
+pub use reader::*;
+pub use register::*;
+pub use repr::*;
+pub use slice::*;
+pub use swap::*;
+pub use typed::*;
+pub use write::*;
+pub use writer::*;
+
+/// derive [`Endianness`](trait@Endianness) and [`EndianSerialize`] for a
+/// struct, converting and serializing it field by field
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{EndianSerialize, Endianness, EndiannessType};
+///
+/// #[derive(Endianness, Debug, PartialEq)]
+/// struct Header {
+///     kind: u16,
+///     len: u32,
+/// }
+///
+/// let mut buf = [0u8; 6];
+/// let header = Header { kind: 1, len: 0x0203 };
+/// assert_eq!(header.write_bytes(&mut buf, EndiannessType::BigEndian), Ok(6));
+/// assert_eq!(buf, [0x00, 0x01, 0x00, 0x00, 0x02, 0x03]);
+/// ```
+#[cfg(feature = "derive")]
+pub use endbyte_derive::Endianness;
+
+use swap::Swap;
+
 /// represents the byte order of the host system
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EndiannessType {
     /// big endian byte order (most significant byte first)
     BigEndian,
@@ -11,7 +91,7 @@ pub enum EndiannessType {
 }
 
 #[inline]
-fn get_local_endianness() -> EndiannessType {
+const fn get_local_endianness() -> EndiannessType {
     #[cfg(target_endian = "big")]
     {
         EndiannessType::BigEndian
@@ -22,6 +102,90 @@ fn get_local_endianness() -> EndiannessType {
     }
 }
 
+/// returns the byte order of the host
+///
+/// useful for formats that store data in native order along with a flag
+/// saying which order that was.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{host_endianness, EndiannessType};
+///
+/// let flag = match host_endianness() {
+///     EndiannessType::BigEndian => b'B',
+///     EndiannessType::LittleEndian => b'L',
+/// };
+/// assert_eq!(flag == b'L', cfg!(target_endian = "little"));
+/// ```
+#[inline]
+pub const fn host_endianness() -> EndiannessType {
+    get_local_endianness()
+}
+
+/// returns `true` if the host is big endian
+///
+/// # examples
+///
+/// ```
+/// use endbyte::is_big_endian;
+///
+/// const TAG: u8 = if is_big_endian() { b'B' } else { b'L' };
+/// assert_eq!(TAG == b'B', cfg!(target_endian = "big"));
+/// ```
+#[inline]
+pub const fn is_big_endian() -> bool {
+    cfg!(target_endian = "big")
+}
+
+/// returns `true` if the host is little endian
+#[inline]
+pub const fn is_little_endian() -> bool {
+    cfg!(target_endian = "little")
+}
+
+/// returns `true` if converting between host byte order and `order` needs a byte swap
+///
+/// this is a `const fn`, so it can be used to make decisions at compile time.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{needs_swap, EndiannessType};
+///
+/// const NEEDS: bool = needs_swap(EndiannessType::BigEndian);
+/// assert_eq!(NEEDS, cfg!(target_endian = "little"));
+/// ```
+#[inline]
+pub const fn needs_swap(order: EndiannessType) -> bool {
+    match order {
+        EndiannessType::BigEndian => cfg!(target_endian = "little"),
+        EndiannessType::LittleEndian => cfg!(target_endian = "big"),
+    }
+}
+
+/// returns `true` if converting a value stored in order `a` to order `b` needs a byte swap
+///
+/// unlike [`needs_swap`] this does not involve the host order, which makes it
+/// useful when planning conversions between two external formats.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{swap_needed_between, EndiannessType};
+///
+/// const SWAP: bool = swap_needed_between(EndiannessType::BigEndian, EndiannessType::LittleEndian);
+/// assert!(SWAP);
+/// ```
+#[inline]
+pub const fn swap_needed_between(a: EndiannessType, b: EndiannessType) -> bool {
+    !matches!(
+        (a, b),
+        (EndiannessType::BigEndian, EndiannessType::BigEndian)
+            | (EndiannessType::LittleEndian, EndiannessType::LittleEndian)
+    )
+}
+
 /// trait for converting between host and network byte orders
 ///
 /// this trait provides methods to convert integer values between the host's
@@ -41,6 +205,22 @@ fn get_local_endianness() -> EndiannessType {
 /// assert_eq!(big_endian.big_endian_to_host(), value);
 /// assert_eq!(little_endian.little_endian_to_host(), value);
 /// ```
+///
+/// the methods take `self` by value and return `Self`, so there is no impl for
+/// `&T`. the built-in impls are all for `Copy` types, so calling a method
+/// through a reference copies the value out and returns an owned result. a
+/// derived struct that is not `Copy` has to be cloned or moved instead:
+///
+/// ```
+/// use endbyte::Endianness;
+///
+/// let value = 0x1234u16;
+/// let by_ref: u16 = (&value).host_to_big_endian();
+/// assert_eq!(by_ref, value.host_to_big_endian());
+/// ```
+///
+/// trait methods cannot be called in const contexts on stable rust. use the
+/// free `const fn` helpers such as [`u32_to_be`] to build constant tables.
 pub trait Endianness {
     /// convert from host byte order to big endian
     fn host_to_big_endian(self) -> Self;
@@ -53,6 +233,140 @@ pub trait Endianness {
 
     /// convert from little endian to host byte order
     fn little_endian_to_host(self) -> Self;
+
+    /// unconditionally reverse the byte order, whatever the host order is
+    ///
+    /// use this when the order of a value is only known at runtime, for
+    /// example from a byte order mark. single-byte types are returned as is.
+    ///
+    /// # examples
+    ///
+    /// ```
+    /// use endbyte::Endianness;
+    ///
+    /// assert_eq!(0x1234u16.swap_endianness(), 0x3412);
+    /// ```
+    ///
+    /// the default converts to the order opposite the host's, which is a swap
+    /// for any implementation of the other methods. the integer impls
+    /// override it with a direct byte swap.
+    fn swap_endianness(self) -> Self
+    where
+        Self: Sized,
+    {
+        match host_endianness() {
+            EndiannessType::LittleEndian => self.host_to_big_endian(),
+            EndiannessType::BigEndian => self.host_to_little_endian(),
+        }
+    }
+
+    /// convert from host byte order to `target`
+    ///
+    /// useful when the order is only known at runtime, for example from a
+    /// header flag.
+    ///
+    /// # examples
+    ///
+    /// ```
+    /// use endbyte::{Endianness, EndiannessType};
+    ///
+    /// let order = EndiannessType::BigEndian;
+    /// assert_eq!(0x1234u16.to_endianness(order), 0x1234u16.host_to_big_endian());
+    /// ```
+    #[inline]
+    fn to_endianness(self, target: EndiannessType) -> Self
+    where
+        Self: Sized,
+    {
+        match target {
+            EndiannessType::BigEndian => self.host_to_big_endian(),
+            EndiannessType::LittleEndian => self.host_to_little_endian(),
+        }
+    }
+
+    /// convert from `source` byte order to host byte order
+    ///
+    /// # examples
+    ///
+    /// ```
+    /// use endbyte::{Endianness, EndiannessType};
+    ///
+    /// let raw = u16::from_ne_bytes([0x12, 0x34]);
+    /// assert_eq!(raw.from_endianness(EndiannessType::BigEndian), 0x1234);
+    /// ```
+    // mirrors to_endianness, so it takes self despite the from_ prefix
+    #[allow(clippy::wrong_self_convention)]
+    #[inline]
+    fn from_endianness(self, source: EndiannessType) -> Self
+    where
+        Self: Sized,
+    {
+        match source {
+            EndiannessType::BigEndian => self.big_endian_to_host(),
+            EndiannessType::LittleEndian => self.little_endian_to_host(),
+        }
+    }
+}
+
+/// serialization of [`Endianness`] values to and from byte slices
+///
+/// kept apart from [`Endianness`] so that hand-written conversion impls do
+/// not have to provide it. it is implemented for every type with a built-in
+/// [`Endianness`] impl and by `#[derive(Endianness)]`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{EndianSerialize, EndiannessType};
+///
+/// let mut buf = [0u8; 4];
+/// assert_eq!(0x1234u16.write_bytes(&mut buf, EndiannessType::LittleEndian), Ok(2));
+/// assert_eq!(u16::from_bytes(&buf, EndiannessType::LittleEndian), Ok(0x1234));
+/// ```
+pub trait EndianSerialize: Endianness {
+    /// size of the serialized value in bytes
+    const BYTES: usize;
+
+    /// read a value stored in `order` from the front of `bytes`
+    ///
+    /// any bytes beyond the first [`BYTES`](EndianSerialize::BYTES) are ignored.
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if `bytes` is too short.
+    ///
+    /// # examples
+    ///
+    /// ```
+    /// use endbyte::{EndianSerialize, EndiannessType};
+    ///
+    /// let bytes = [0x12, 0x34];
+    /// assert_eq!(u16::from_bytes(&bytes, EndiannessType::BigEndian), Ok(0x1234));
+    /// assert_eq!(u16::from_bytes(&bytes, EndiannessType::LittleEndian), Ok(0x3412));
+    /// ```
+    fn from_bytes(bytes: &[u8], order: EndiannessType) -> Result<Self, EndianError>
+    where
+        Self: Sized;
+
+    /// write the value in `order` to the front of `buf`
+    ///
+    /// returns the number of bytes written, which is always
+    /// [`BYTES`](EndianSerialize::BYTES).
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if `buf` is too short.
+    ///
+    /// # examples
+    ///
+    /// ```
+    /// use endbyte::{EndianSerialize, EndiannessType};
+    ///
+    /// let mut buf = [0u8; 2];
+    /// assert_eq!(0x1234u16.write_bytes(&mut buf, EndiannessType::BigEndian), Ok(2));
+    /// assert_eq!(buf, [0x12, 0x34]);
+    /// ```
+    fn write_bytes(self, buf: &mut [u8], order: EndiannessType) -> Result<usize, EndianError>;
 }
 
 // impl Endianness for unsigned integers
@@ -63,13 +377,13 @@ macro_rules! impl_endianness_unsigned {
                 fn host_to_big_endian(self) -> Self {
                     match get_local_endianness() {
                         EndiannessType::BigEndian => self,
-                        EndiannessType::LittleEndian => self.swap_bytes(),
+                        EndiannessType::LittleEndian => Swap::swap(self),
                     }
                 }
 
                 fn host_to_little_endian(self) -> Self {
                     match get_local_endianness() {
-                        EndiannessType::BigEndian => self.swap_bytes(),
+                        EndiannessType::BigEndian => Swap::swap(self),
                         EndiannessType::LittleEndian => self,
                     }
                 }
@@ -77,16 +391,40 @@ macro_rules! impl_endianness_unsigned {
                 fn big_endian_to_host(self) -> Self {
                     match get_local_endianness() {
                         EndiannessType::BigEndian => self,
-                        EndiannessType::LittleEndian => self.swap_bytes(),
+                        EndiannessType::LittleEndian => Swap::swap(self),
                     }
                 }
 
                 fn little_endian_to_host(self) -> Self {
                     match get_local_endianness() {
-                        EndiannessType::BigEndian => self.swap_bytes(),
+                        EndiannessType::BigEndian => Swap::swap(self),
                         EndiannessType::LittleEndian => self,
                     }
                 }
+
+                fn swap_endianness(self) -> Self {
+                    Swap::swap(self)
+                }
+            }
+
+            impl EndianSerialize for $t {
+                const BYTES: usize = core::mem::size_of::<$t>();
+
+                fn from_bytes(bytes: &[u8], order: EndiannessType) -> Result<Self, EndianError> {
+                    let raw = <$t>::from_ne_bytes(read::take(bytes)?);
+                    Ok(match order {
+                        EndiannessType::BigEndian => raw.big_endian_to_host(),
+                        EndiannessType::LittleEndian => raw.little_endian_to_host(),
+                    })
+                }
+
+                fn write_bytes(self, buf: &mut [u8], order: EndiannessType) -> Result<usize, EndianError> {
+                    let raw = match order {
+                        EndiannessType::BigEndian => self.host_to_big_endian(),
+                        EndiannessType::LittleEndian => self.host_to_little_endian(),
+                    };
+                    write::put(buf, raw.to_ne_bytes())
+                }
             }
         )*
     };
@@ -109,6 +447,22 @@ impl Endianness for u8 {
     fn little_endian_to_host(self) -> Self {
         self
     }
+
+    fn swap_endianness(self) -> Self {
+        self
+    }
+}
+
+impl EndianSerialize for u8 {
+    const BYTES: usize = 1;
+
+    fn from_bytes(bytes: &[u8], _order: EndiannessType) -> Result<Self, EndianError> {
+        Ok(u8::from_ne_bytes(read::take(bytes)?))
+    }
+
+    fn write_bytes(self, buf: &mut [u8], _order: EndiannessType) -> Result<usize, EndianError> {
+        write::put(buf, self.to_ne_bytes())
+    }
 }
 
 // no byte swapping needed for single-byte types
@@ -128,11 +482,74 @@ impl Endianness for i8 {
     fn little_endian_to_host(self) -> Self {
         self
     }
+
+    fn swap_endianness(self) -> Self {
+        self
+    }
+}
+
+impl EndianSerialize for i8 {
+    const BYTES: usize = 1;
+
+    fn from_bytes(bytes: &[u8], _order: EndiannessType) -> Result<Self, EndianError> {
+        Ok(i8::from_ne_bytes(read::take(bytes)?))
+    }
+
+    fn write_bytes(self, buf: &mut [u8], _order: EndiannessType) -> Result<usize, EndianError> {
+        write::put(buf, self.to_ne_bytes())
+    }
 }
 
 // impl Endianness for multi-byte unsigned integers
 impl_endianness_unsigned!(u16, u32, u64, u128);
 
+// fixed width unsigned integer with the same size as `usize`
+#[cfg(target_pointer_width = "16")]
+type PointerSized = u16;
+#[cfg(target_pointer_width = "32")]
+type PointerSized = u32;
+#[cfg(target_pointer_width = "64")]
+type PointerSized = u64;
+
+/// **the width of `usize` is platform dependent.** it is 4 bytes on 32-bit
+/// targets and 8 bytes on 64-bit targets, so [`BYTES`](EndianSerialize::BYTES) and
+/// the serialized form change with the host. values written by one host only
+/// round-trip on hosts with the same pointer width. prefer a fixed width type
+/// such as `u32` or `u64` for anything stored or sent over a network.
+impl Endianness for usize {
+    fn host_to_big_endian(self) -> Self {
+        (self as PointerSized).host_to_big_endian() as usize
+    }
+
+    fn host_to_little_endian(self) -> Self {
+        (self as PointerSized).host_to_little_endian() as usize
+    }
+
+    fn big_endian_to_host(self) -> Self {
+        (self as PointerSized).big_endian_to_host() as usize
+    }
+
+    fn little_endian_to_host(self) -> Self {
+        (self as PointerSized).little_endian_to_host() as usize
+    }
+
+    fn swap_endianness(self) -> Self {
+        (self as PointerSized).swap_endianness() as usize
+    }
+}
+
+impl EndianSerialize for usize {
+    const BYTES: usize = PointerSized::BYTES;
+
+    fn from_bytes(bytes: &[u8], order: EndiannessType) -> Result<Self, EndianError> {
+        PointerSized::from_bytes(bytes, order).map(|value| value as usize)
+    }
+
+    fn write_bytes(self, buf: &mut [u8], order: EndiannessType) -> Result<usize, EndianError> {
+        (self as PointerSized).write_bytes(buf, order)
+    }
+}
+
 // impl Endianness for signed integers
 macro_rules! impl_endianness_signed {
     ($($t:ty),*) => {
@@ -169,6 +586,27 @@ macro_rules! impl_endianness_signed {
                             .to_ne_bytes()
                     )
                 }
+
+                fn swap_endianness(self) -> Self {
+                    <$t>::from_ne_bytes(
+                        (self as <$t as ToUnsigned>::Unsigned)
+                            .swap_endianness()
+                            .to_ne_bytes()
+                    )
+                }
+            }
+
+            impl EndianSerialize for $t {
+                const BYTES: usize = core::mem::size_of::<$t>();
+
+                fn from_bytes(bytes: &[u8], order: EndiannessType) -> Result<Self, EndianError> {
+                    <<$t as ToUnsigned>::Unsigned>::from_bytes(bytes, order)
+                        .map(|value| <$t>::from_ne_bytes(value.to_ne_bytes()))
+                }
+
+                fn write_bytes(self, buf: &mut [u8], order: EndiannessType) -> Result<usize, EndianError> {
+                    (self as <$t as ToUnsigned>::Unsigned).write_bytes(buf, order)
+                }
             }
         )*
     };
@@ -191,10 +629,185 @@ impl ToUnsigned for i64 {
 impl ToUnsigned for i128 {
     type Unsigned = u128;
 }
+impl ToUnsigned for isize {
+    type Unsigned = usize;
+}
 
 // impl Endianness for multi-byte signed integers
 impl_endianness_signed!(i16, i32, i64, i128);
 
+// the width of isize is platform dependent, see the usize impl
+impl_endianness_signed!(isize);
+
+// impl Endianness for nonzero integers by converting the inner value. a byte
+// swap only moves bytes around, so a nonzero value never swaps to zero
+macro_rules! impl_endianness_nonzero {
+    ($($t:ty),*) => {
+        $(
+            impl Endianness for core::num::NonZero<$t> {
+                fn host_to_big_endian(self) -> Self {
+                    Self::new(self.get().host_to_big_endian()).expect(NONZERO_SWAP)
+                }
+
+                fn host_to_little_endian(self) -> Self {
+                    Self::new(self.get().host_to_little_endian()).expect(NONZERO_SWAP)
+                }
+
+                fn big_endian_to_host(self) -> Self {
+                    Self::new(self.get().big_endian_to_host()).expect(NONZERO_SWAP)
+                }
+
+                fn little_endian_to_host(self) -> Self {
+                    Self::new(self.get().little_endian_to_host()).expect(NONZERO_SWAP)
+                }
+
+                fn swap_endianness(self) -> Self {
+                    Self::new(self.get().swap_endianness()).expect(NONZERO_SWAP)
+                }
+            }
+
+            impl EndianSerialize for core::num::NonZero<$t> {
+                const BYTES: usize = <$t as EndianSerialize>::BYTES;
+
+                fn from_bytes(bytes: &[u8], order: EndiannessType) -> Result<Self, EndianError> {
+                    Self::new(<$t>::from_bytes(bytes, order)?).ok_or(EndianError::ZeroValue)
+                }
+
+                fn write_bytes(self, buf: &mut [u8], order: EndiannessType) -> Result<usize, EndianError> {
+                    self.get().write_bytes(buf, order)
+                }
+            }
+        )*
+    };
+}
+
+const NONZERO_SWAP: &str = "byte swap of a nonzero value is nonzero";
+
+impl_endianness_nonzero!(u8, u16, u32, u64, u128, usize);
+impl_endianness_nonzero!(i8, i16, i32, i64, i128, isize);
+
+// impl Endianness for the arithmetic wrappers by converting the inner value
+macro_rules! impl_endianness_wrapper {
+    ($($wrapper:ident),*) => {
+        $(
+            impl<T: Endianness> Endianness for core::num::$wrapper<T> {
+                fn host_to_big_endian(self) -> Self {
+                    core::num::$wrapper(self.0.host_to_big_endian())
+                }
+
+                fn host_to_little_endian(self) -> Self {
+                    core::num::$wrapper(self.0.host_to_little_endian())
+                }
+
+                fn big_endian_to_host(self) -> Self {
+                    core::num::$wrapper(self.0.big_endian_to_host())
+                }
+
+                fn little_endian_to_host(self) -> Self {
+                    core::num::$wrapper(self.0.little_endian_to_host())
+                }
+
+                fn swap_endianness(self) -> Self {
+                    core::num::$wrapper(self.0.swap_endianness())
+                }
+            }
+
+            impl<T: EndianSerialize> EndianSerialize for core::num::$wrapper<T> {
+                const BYTES: usize = T::BYTES;
+
+                fn from_bytes(bytes: &[u8], order: EndiannessType) -> Result<Self, EndianError> {
+                    T::from_bytes(bytes, order).map(core::num::$wrapper)
+                }
+
+                fn write_bytes(self, buf: &mut [u8], order: EndiannessType) -> Result<usize, EndianError> {
+                    self.0.write_bytes(buf, order)
+                }
+            }
+        )*
+    };
+}
+
+impl_endianness_wrapper!(Wrapping, Saturating);
+
+// impl Endianness for tuples by converting every element. the serialized form
+// is the elements back to back in order, like a derived struct
+macro_rules! impl_endianness_tuple {
+    ($(($($name:ident $index:tt),+))*) => {
+        $(
+            impl<$($name: Endianness),+> Endianness for ($($name,)+) {
+                fn host_to_big_endian(self) -> Self {
+                    ($(self.$index.host_to_big_endian(),)+)
+                }
+
+                fn host_to_little_endian(self) -> Self {
+                    ($(self.$index.host_to_little_endian(),)+)
+                }
+
+                fn big_endian_to_host(self) -> Self {
+                    ($(self.$index.big_endian_to_host(),)+)
+                }
+
+                fn little_endian_to_host(self) -> Self {
+                    ($(self.$index.little_endian_to_host(),)+)
+                }
+
+                fn swap_endianness(self) -> Self {
+                    ($(self.$index.swap_endianness(),)+)
+                }
+            }
+
+            impl<$($name: EndianSerialize),+> EndianSerialize for ($($name,)+) {
+                const BYTES: usize = 0 $(+ <$name as EndianSerialize>::BYTES)+;
+
+                // the last element leaves its offset update unread
+                #[allow(unused_assignments)]
+                fn from_bytes(bytes: &[u8], order: EndiannessType) -> Result<Self, EndianError> {
+                    if bytes.len() < Self::BYTES {
+                        return Err(EndianError::UnexpectedEof {
+                            needed: Self::BYTES,
+                            available: bytes.len(),
+                        });
+                    }
+
+                    let mut offset = 0;
+                    Ok(($(
+                        {
+                            let value = <$name as EndianSerialize>::from_bytes(&bytes[offset..], order)?;
+                            offset += <$name as EndianSerialize>::BYTES;
+                            value
+                        },
+                    )+))
+                }
+
+                #[allow(unused_assignments)]
+                fn write_bytes(self, buf: &mut [u8], order: EndiannessType) -> Result<usize, EndianError> {
+                    if buf.len() < Self::BYTES {
+                        return Err(EndianError::UnexpectedEof {
+                            needed: Self::BYTES,
+                            available: buf.len(),
+                        });
+                    }
+
+                    let mut offset = 0;
+                    $(offset += self.$index.write_bytes(&mut buf[offset..], order)?;)+
+                    Ok(Self::BYTES)
+                }
+            }
+        )*
+    };
+}
+
+impl_endianness_tuple! {
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,6 +824,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_host_endianness() {
+        const HOST: EndiannessType = host_endianness();
+
+        assert_eq!(HOST, get_local_endianness());
+        assert!(!needs_swap(HOST));
+
+        // a value read in the host order matches its native bytes
+        let bytes = [0x12, 0x34, 0x56, 0x78];
+        assert_eq!(u32::from_bytes(&bytes, HOST), Ok(u32::from_ne_bytes(bytes)));
+    }
+
+    #[test]
+    fn test_is_big_little_endian() {
+        const BIG: bool = is_big_endian();
+        const LITTLE: bool = is_little_endian();
+        const BOM: [u8; 2] = if is_big_endian() {
+            [0xfe, 0xff]
+        } else {
+            [0xff, 0xfe]
+        };
+
+        assert_ne!(BIG, LITTLE);
+        assert_eq!(BIG, host_endianness() == EndiannessType::BigEndian);
+        assert_eq!(LITTLE, host_endianness() == EndiannessType::LittleEndian);
+
+        // a utf-16 byte order mark written in host order
+        assert_eq!(BOM, 0xfeffu16.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_needs_swap() {
+        const NEEDS_BIG: bool = needs_swap(EndiannessType::BigEndian);
+        const NEEDS_LITTLE: bool = needs_swap(EndiannessType::LittleEndian);
+
+        // exactly one of the two orders differs from the host
+        assert_ne!(NEEDS_BIG, NEEDS_LITTLE);
+        assert_eq!(
+            NEEDS_BIG,
+            get_local_endianness() == EndiannessType::LittleEndian
+        );
+        assert_eq!(
+            NEEDS_LITTLE,
+            get_local_endianness() == EndiannessType::BigEndian
+        );
+
+        // a swap is needed exactly when the converted value differs
+        assert_eq!(NEEDS_BIG, 0x1234u16.host_to_big_endian() != 0x1234);
+    }
+
+    #[test]
+    fn test_swap_needed_between() {
+        use EndiannessType::{BigEndian, LittleEndian};
+
+        assert!(!swap_needed_between(BigEndian, BigEndian));
+        assert!(!swap_needed_between(LittleEndian, LittleEndian));
+        assert!(swap_needed_between(BigEndian, LittleEndian));
+        assert!(swap_needed_between(LittleEndian, BigEndian));
+
+        // relative to the host it agrees with needs_swap
+        let host = get_local_endianness();
+        assert_eq!(swap_needed_between(host, BigEndian), needs_swap(BigEndian));
+        assert_eq!(
+            swap_needed_between(host, LittleEndian),
+            needs_swap(LittleEndian)
+        );
+    }
+
     #[test]
     fn test_swap_bytes() {
         assert_eq!(0x1234u16.swap_bytes(), 0x3412);
@@ -386,4 +1067,628 @@ mod tests {
         assert_eq!(value_i8.big_endian_to_host(), value_i8);
         assert_eq!(value_i8.little_endian_to_host(), value_i8);
     }
+
+    #[test]
+    fn test_from_bytes_generic() {
+        fn read_both<T: EndianSerialize>(bytes: &[u8]) -> (T, T) {
+            (
+                T::from_bytes(bytes, EndiannessType::BigEndian).unwrap(),
+                T::from_bytes(bytes, EndiannessType::LittleEndian).unwrap(),
+            )
+        }
+
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+
+        assert_eq!(read_both::<u16>(&bytes), (0x0102, 0x0201));
+        assert_eq!(
+            read_both::<u64>(&bytes),
+            (0x0102030405060708, 0x0807060504030201)
+        );
+        assert_eq!(read_both::<i16>(&[0xff, 0xfe]), (-2, -257));
+        assert_eq!(read_both::<u8>(&bytes), (0x01, 0x01));
+    }
+
+    #[test]
+    fn test_write_bytes() {
+        let mut buf = [0u8; 8];
+
+        assert_eq!(
+            0x0102u16.write_bytes(&mut buf, EndiannessType::BigEndian),
+            Ok(2)
+        );
+        assert_eq!(buf[..2], [0x01, 0x02]);
+
+        assert_eq!(
+            (-2i32).write_bytes(&mut buf, EndiannessType::LittleEndian),
+            Ok(4)
+        );
+        assert_eq!(buf[..4], [0xfe, 0xff, 0xff, 0xff]);
+
+        // writing then reading in the same order round-trips
+        let value = 0x0102030405060708u64;
+        value
+            .write_bytes(&mut buf, EndiannessType::BigEndian)
+            .unwrap();
+        assert_eq!(u64::from_bytes(&buf, EndiannessType::BigEndian), Ok(value));
+
+        assert_eq!(
+            value.write_bytes(&mut buf[..4], EndiannessType::BigEndian),
+            Err(EndianError::UnexpectedEof {
+                needed: 8,
+                available: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_length_check() {
+        assert_eq!(u16::BYTES, 2);
+        assert_eq!(i64::BYTES, 8);
+        assert_eq!(u128::BYTES, 16);
+
+        assert_eq!(
+            u64::from_bytes(&[0x01, 0x02, 0x03], EndiannessType::BigEndian),
+            Err(EndianError::UnexpectedEof {
+                needed: 8,
+                available: 3
+            })
+        );
+        assert_eq!(
+            u8::from_bytes(&[], EndiannessType::LittleEndian),
+            Err(EndianError::UnexpectedEof {
+                needed: 1,
+                available: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_conversion_through_reference() {
+        let value = 0x12345678u32;
+        let reference = &value;
+
+        // auto-deref copies the value out, so the result is an owned u32
+        let converted: u32 = reference.host_to_big_endian();
+        assert_eq!(converted, value.host_to_big_endian());
+        assert_eq!(
+            reference.host_to_little_endian(),
+            value.host_to_little_endian()
+        );
+
+        // generic code over references can copy out explicitly
+        fn to_big<T: Endianness + Copy>(value: &T) -> T {
+            value.host_to_big_endian()
+        }
+        assert_eq!(to_big(&value).big_endian_to_host(), value);
+    }
+
+    #[test]
+    fn test_pointer_sized_round_trip() {
+        let value = 0x1234usize;
+        assert_eq!(value.host_to_big_endian().big_endian_to_host(), value);
+        assert_eq!(value.host_to_little_endian().little_endian_to_host(), value);
+        assert_eq!(usize::BYTES, core::mem::size_of::<usize>());
+        assert_eq!(isize::BYTES, core::mem::size_of::<isize>());
+
+        let mut buf = [0u8; 16];
+        let len = (-2isize)
+            .write_bytes(&mut buf, EndiannessType::BigEndian)
+            .unwrap();
+        assert_eq!(
+            isize::from_bytes(&buf[..len], EndiannessType::BigEndian),
+            Ok(-2)
+        );
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_pointer_sized_64() {
+        let mut buf = [0u8; 8];
+        assert_eq!(
+            0x0102usize.write_bytes(&mut buf, EndiannessType::BigEndian),
+            Ok(8)
+        );
+        assert_eq!(buf, [0, 0, 0, 0, 0, 0, 0x01, 0x02]);
+        assert_eq!(
+            isize::from_bytes(&[0xff; 8], EndiannessType::LittleEndian),
+            Ok(-1)
+        );
+        assert_eq!(
+            usize::from_bytes(&buf[..4], EndiannessType::BigEndian),
+            Err(EndianError::UnexpectedEof {
+                needed: 8,
+                available: 4
+            })
+        );
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    fn test_pointer_sized_32() {
+        let mut buf = [0u8; 4];
+        assert_eq!(
+            0x0102usize.write_bytes(&mut buf, EndiannessType::BigEndian),
+            Ok(4)
+        );
+        assert_eq!(buf, [0, 0, 0x01, 0x02]);
+        assert_eq!(
+            isize::from_bytes(&[0xff; 4], EndiannessType::LittleEndian),
+            Ok(-1)
+        );
+        assert_eq!(
+            usize::from_bytes(&buf[..2], EndiannessType::BigEndian),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_swap_endianness() {
+        // the result does not depend on the host order
+        assert_eq!(0x1234u16.swap_endianness(), 0x3412);
+        assert_eq!(0x12345678u32.swap_endianness(), 0x78563412);
+        assert_eq!(0x0102030405060708u64.swap_endianness(), 0x0807060504030201);
+        assert_eq!(1u128.swap_endianness(), 1 << 120);
+        assert_eq!(0x00ffi16.swap_endianness(), -256);
+        assert_eq!((-2i32).swap_endianness(), 0xfeff_ffffu32 as i32);
+        assert_eq!(0x0102usize.swap_endianness().swap_endianness(), 0x0102);
+
+        // single bytes are left alone
+        assert_eq!(0xabu8.swap_endianness(), 0xab);
+        assert_eq!((-5i8).swap_endianness(), -5);
+
+        // swapping a converted value gives the other order
+        assert_eq!(
+            0x1234u16.host_to_big_endian().swap_endianness(),
+            0x1234u16.host_to_little_endian()
+        );
+    }
+
+    #[test]
+    fn test_runtime_endianness() {
+        let value = 0x12345678u32;
+
+        let big = value.to_endianness(EndiannessType::BigEndian);
+        assert_eq!(big, value.host_to_big_endian());
+        assert_eq!(big.to_ne_bytes(), [0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(big.from_endianness(EndiannessType::BigEndian), value);
+
+        let little = value.to_endianness(EndiannessType::LittleEndian);
+        assert_eq!(little, value.host_to_little_endian());
+        assert_eq!(little.to_ne_bytes(), [0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(little.from_endianness(EndiannessType::LittleEndian), value);
+
+        // signed values round-trip through both orders
+        for order in [EndiannessType::BigEndian, EndiannessType::LittleEndian] {
+            assert_eq!((-300i16).to_endianness(order).from_endianness(order), -300);
+        }
+    }
+
+    #[cfg(feature = "derive")]
+    mod derive {
+        use crate::{EndianError, EndianSerialize, Endianness, EndiannessType};
+
+        #[derive(Endianness, Debug, Clone, Copy, PartialEq)]
+        struct Point {
+            x: u16,
+            y: i32,
+        }
+
+        #[derive(Endianness, Debug, Clone, Copy, PartialEq)]
+        struct Packet {
+            origin: Point,
+            #[endian(skip)]
+            tag: u8,
+            seq: u64,
+        }
+
+        #[derive(Endianness, Debug, Clone, Copy, PartialEq)]
+        struct Pair(pub u16, u32);
+
+        #[derive(Endianness, Debug, Clone, Copy, PartialEq)]
+        struct Marker;
+
+        #[cfg(feature = "canonical-hash")]
+        #[test]
+        fn test_derive_canonical_hash() {
+            use crate::CanonicalHasher;
+
+            let packet = Packet {
+                origin: Point { x: 0x0102, y: -2 },
+                tag: 9,
+                seq: 0x0304,
+            };
+
+            let mut buf = [0u8; 14];
+            packet
+                .write_bytes(&mut buf, EndiannessType::BigEndian)
+                .unwrap();
+            assert_eq!(packet.canonical_hash(), CanonicalHasher::hash_bytes(&buf));
+
+            // precomputed so that a change in byte order is caught on any host
+            assert_eq!(packet.canonical_hash(), 0x1ce7_5087_3b00_1656);
+
+            // skipped fields do not take part in the hash
+            assert_eq!(
+                Packet { tag: 0, ..packet }.canonical_hash(),
+                packet.canonical_hash()
+            );
+            assert_eq!(
+                Pair(1, 2).canonical_hash(),
+                CanonicalHasher::hash_bytes(&[0, 1, 0, 0, 0, 2])
+            );
+            assert_eq!(Marker.canonical_hash(), CanonicalHasher::hash_bytes(&[]));
+        }
+
+        #[test]
+        fn test_derive_round_trip_big_endian() {
+            let point = Point { x: 0x1234, y: -2 };
+
+            let big = point.host_to_big_endian();
+            assert_eq!(big.x, 0x1234u16.host_to_big_endian());
+            assert_eq!(big.y, (-2i32).host_to_big_endian());
+            assert_eq!(big.big_endian_to_host(), point);
+
+            let mut buf = [0u8; 6];
+            assert_eq!(Point::BYTES, 6);
+            assert_eq!(
+                point.write_bytes(&mut buf, EndiannessType::BigEndian),
+                Ok(6)
+            );
+            assert_eq!(buf, [0x12, 0x34, 0xff, 0xff, 0xff, 0xfe]);
+            assert_eq!(
+                Point::from_bytes(&buf, EndiannessType::BigEndian),
+                Ok(point)
+            );
+        }
+
+        #[test]
+        fn test_derive_nested_and_skip() {
+            let packet = Packet {
+                origin: Point { x: 1, y: 2 },
+                tag: 0xaa,
+                seq: 0x0102030405060708,
+            };
+
+            // skipped fields are neither converted nor serialized
+            let swapped = packet.swap_endianness();
+            assert_eq!(swapped.origin.x, 0x0100);
+            assert_eq!(swapped.tag, 0xaa);
+            assert_eq!(swapped.seq, 0x0807060504030201);
+            assert_eq!(swapped.swap_endianness(), packet);
+            assert_eq!(Packet::BYTES, 14);
+
+            let mut buf = [0u8; 14];
+            packet
+                .write_bytes(&mut buf, EndiannessType::LittleEndian)
+                .unwrap();
+            assert_eq!(buf[..6], [0x01, 0x00, 0x02, 0x00, 0x00, 0x00]);
+
+            let read = Packet::from_bytes(&buf, EndiannessType::LittleEndian).unwrap();
+            assert_eq!(read.origin, packet.origin);
+            assert_eq!(read.seq, packet.seq);
+            assert_eq!(read.tag, 0);
+        }
+
+        #[test]
+        fn test_derive_tuple_and_unit() {
+            let pair = Pair(0x0102, 0x03040506);
+            assert_eq!(
+                pair.to_endianness(EndiannessType::BigEndian)
+                    .from_endianness(EndiannessType::BigEndian),
+                pair
+            );
+
+            let mut buf = [0u8; 6];
+            assert_eq!(pair.write_bytes(&mut buf, EndiannessType::BigEndian), Ok(6));
+            assert_eq!(buf, [0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+
+            assert_eq!(Marker::BYTES, 0);
+            assert_eq!(Marker.host_to_little_endian(), Marker);
+            assert_eq!(
+                Marker::from_bytes(&[], EndiannessType::BigEndian),
+                Ok(Marker)
+            );
+        }
+
+        #[test]
+        fn test_derive_length_checks() {
+            let mut buf = [0u8; 5];
+            assert_eq!(
+                Point { x: 1, y: 2 }.write_bytes(&mut buf, EndiannessType::BigEndian),
+                Err(EndianError::UnexpectedEof {
+                    needed: 6,
+                    available: 5
+                })
+            );
+            assert_eq!(buf, [0; 5]);
+            assert_eq!(
+                Packet::from_bytes(&[0; 13], EndiannessType::BigEndian),
+                Err(EndianError::UnexpectedEof {
+                    needed: 14,
+                    available: 13
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_nonzero_round_trip() {
+        use core::num::{NonZeroI32, NonZeroU16, NonZeroU64};
+
+        let port = NonZeroU16::new(0x1f90).unwrap();
+        assert_eq!(port.host_to_big_endian().get().to_ne_bytes(), [0x1f, 0x90]);
+        assert_eq!(port.host_to_big_endian().big_endian_to_host(), port);
+        assert_eq!(port.host_to_little_endian().little_endian_to_host(), port);
+        assert_eq!(port.swap_endianness().get(), 0x901f);
+
+        let id = NonZeroU64::new(0x0102030405060708).unwrap();
+        let mut buf = [0u8; 8];
+        assert_eq!(id.write_bytes(&mut buf, EndiannessType::BigEndian), Ok(8));
+        assert_eq!(buf, [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(
+            NonZeroU64::from_bytes(&buf, EndiannessType::BigEndian),
+            Ok(id)
+        );
+        assert_eq!(
+            NonZeroU64::from_bytes(&buf, EndiannessType::LittleEndian),
+            Ok(id.swap_endianness())
+        );
+
+        let negative = NonZeroI32::new(-2).unwrap();
+        assert_eq!(negative.host_to_big_endian().big_endian_to_host(), negative);
+        assert_eq!(NonZeroU16::BYTES, 2);
+
+        // the niche is kept
+        assert_eq!(
+            core::mem::size_of::<Option<NonZeroU64>>(),
+            core::mem::size_of::<u64>()
+        );
+    }
+
+    #[test]
+    fn test_nonzero_from_zero() {
+        use core::num::{NonZeroU16, NonZeroU8};
+
+        assert_eq!(
+            NonZeroU16::from_bytes(&[0, 0], EndiannessType::BigEndian),
+            Err(EndianError::ZeroValue)
+        );
+        assert_eq!(
+            NonZeroU8::from_bytes(&[0], EndiannessType::LittleEndian),
+            Err(EndianError::ZeroValue)
+        );
+        assert_eq!(
+            NonZeroU16::from_bytes(&[1], EndiannessType::BigEndian),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_wrapping_round_trip() {
+        use core::num::Wrapping;
+
+        let counter = Wrapping(0x1234u16);
+        assert_eq!(counter.host_to_big_endian().0.to_ne_bytes(), [0x12, 0x34]);
+        assert_eq!(
+            counter.host_to_little_endian().0.to_ne_bytes(),
+            [0x34, 0x12]
+        );
+        assert_eq!(counter.host_to_big_endian().big_endian_to_host(), counter);
+        assert_eq!(
+            counter.host_to_little_endian().little_endian_to_host(),
+            counter
+        );
+        assert_eq!(counter.swap_endianness(), Wrapping(0x3412));
+
+        let mut buf = [0u8; 2];
+        assert_eq!(
+            counter.write_bytes(&mut buf, EndiannessType::BigEndian),
+            Ok(2)
+        );
+        assert_eq!(buf, [0x12, 0x34]);
+        assert_eq!(
+            Wrapping::<u16>::from_bytes(&buf, EndiannessType::BigEndian),
+            Ok(counter)
+        );
+        assert_eq!(Wrapping::<u16>::BYTES, 2);
+    }
+
+    #[test]
+    fn test_saturating_round_trip() {
+        use core::num::Saturating;
+
+        let level = Saturating(-2i32);
+        assert_eq!(level.host_to_big_endian().big_endian_to_host(), level);
+        assert_eq!(
+            Saturating::<i32>::from_bytes(&[0xff, 0xff, 0xff, 0xfe], EndiannessType::BigEndian),
+            Ok(level)
+        );
+    }
+
+    // implements only the four conversions, as code written against 0.1.0
+    // does, without swap_endianness or EndianSerialize
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Legacy(u32);
+
+    impl Endianness for Legacy {
+        fn host_to_big_endian(self) -> Self {
+            Legacy(self.0.host_to_big_endian())
+        }
+
+        fn host_to_little_endian(self) -> Self {
+            Legacy(self.0.host_to_little_endian())
+        }
+
+        fn big_endian_to_host(self) -> Self {
+            Legacy(self.0.big_endian_to_host())
+        }
+
+        fn little_endian_to_host(self) -> Self {
+            Legacy(self.0.little_endian_to_host())
+        }
+    }
+
+    #[test]
+    fn test_default_swap_endianness() {
+        assert_eq!(Legacy(0x12345678).swap_endianness(), Legacy(0x78563412));
+        assert_eq!(
+            Legacy(0x12345678).swap_endianness().swap_endianness(),
+            Legacy(0x12345678)
+        );
+    }
+
+    #[test]
+    fn test_tuple_round_trip() {
+        let header = (0x0102u16, 0x03040506u32);
+        assert_eq!(<(u16, u32)>::BYTES, 6);
+
+        let big = header.host_to_big_endian();
+        assert_eq!(big.0.to_ne_bytes(), [0x01, 0x02]);
+        assert_eq!(big.1.to_ne_bytes(), [0x03, 0x04, 0x05, 0x06]);
+        assert_eq!(big.big_endian_to_host(), header);
+        assert_eq!(
+            header.host_to_little_endian().little_endian_to_host(),
+            header
+        );
+        assert_eq!(header.swap_endianness(), (0x0201, 0x06050403));
+
+        let mut buf = [0u8; 7];
+        assert_eq!(
+            header.write_bytes(&mut buf, EndiannessType::LittleEndian),
+            Ok(6)
+        );
+        assert_eq!(buf, [0x02, 0x01, 0x06, 0x05, 0x04, 0x03, 0x00]);
+        assert_eq!(
+            <(u16, u32)>::from_bytes(&buf, EndiannessType::LittleEndian),
+            Ok(header)
+        );
+    }
+
+    #[test]
+    fn test_tuple_arities() {
+        let single = (-2i16,);
+        assert_eq!(single.host_to_big_endian().0.to_ne_bytes(), [0xff, 0xfe]);
+
+        let wide = (1u8, 2i8, 3u16, 4i16, 5u32, 6i32, 7u64, 8i128);
+        assert_eq!(<(u8, i8, u16, i16, u32, i32, u64, i128)>::BYTES, 38);
+        let mut buf = [0u8; 38];
+        assert_eq!(
+            wide.write_bytes(&mut buf, EndiannessType::BigEndian),
+            Ok(38)
+        );
+        assert_eq!(buf[..6], [1, 2, 0, 3, 0, 4]);
+        assert_eq!(buf[37], 8);
+        assert_eq!(
+            <(u8, i8, u16, i16, u32, i32, u64, i128)>::from_bytes(&buf, EndiannessType::BigEndian),
+            Ok(wide)
+        );
+
+        // short buffers are rejected before anything is read or written
+        let mut short = [0u8; 5];
+        assert_eq!(
+            (0x0102u16, 0x03040506u32).write_bytes(&mut short, EndiannessType::BigEndian),
+            Err(EndianError::UnexpectedEof {
+                needed: 6,
+                available: 5
+            })
+        );
+        assert_eq!(short, [0; 5]);
+        assert_eq!(
+            <(u32, u8)>::from_bytes(&[0; 4], EndiannessType::BigEndian),
+            Err(EndianError::UnexpectedEof {
+                needed: 5,
+                available: 4
+            })
+        );
+    }
+
+    // zero, all ones, min and max through every conversion and both orders
+    mod edge_cases {
+        use super::*;
+
+        macro_rules! assert_edge_cases {
+            ($($t:ty => $all_ones:expr),* $(,)?) => {
+                $(
+                    for value in [0 as $t, $all_ones, <$t>::MIN, <$t>::MAX] {
+                        let be = value.to_be_bytes();
+                        let le = value.to_le_bytes();
+
+                        // in-register conversions
+                        assert_eq!(value.host_to_big_endian().to_ne_bytes(), be);
+                        assert_eq!(value.host_to_little_endian().to_ne_bytes(), le);
+                        assert_eq!(<$t>::from_ne_bytes(be).big_endian_to_host(), value);
+                        assert_eq!(<$t>::from_ne_bytes(le).little_endian_to_host(), value);
+                        assert_eq!(value.swap_endianness(), value.swap_bytes());
+
+                        // buffer conversions
+                        let mut buf = [0xa5u8; 16];
+                        assert_eq!(value.write_bytes(&mut buf, EndiannessType::BigEndian), Ok(be.len()));
+                        assert_eq!(&buf[..be.len()], &be[..]);
+                        assert_eq!(<$t>::from_bytes(&buf, EndiannessType::BigEndian), Ok(value));
+
+                        let mut buf = [0xa5u8; 16];
+                        assert_eq!(value.write_bytes(&mut buf, EndiannessType::LittleEndian), Ok(le.len()));
+                        assert_eq!(&buf[..le.len()], &le[..]);
+                        assert_eq!(<$t>::from_bytes(&buf, EndiannessType::LittleEndian), Ok(value));
+                    }
+
+                    // all-zero and all-ones bytes decode the same in either order
+                    for order in [EndiannessType::BigEndian, EndiannessType::LittleEndian] {
+                        assert_eq!(<$t>::from_bytes(&[0x00; 16], order), Ok(0));
+                        assert_eq!(<$t>::from_bytes(&[0xff; 16], order), Ok($all_ones));
+                    }
+                )*
+            };
+        }
+
+        #[test]
+        fn test_edge_cases_unsigned() {
+            assert_edge_cases!(
+                u8 => u8::MAX,
+                u16 => u16::MAX,
+                u32 => u32::MAX,
+                u64 => u64::MAX,
+                u128 => u128::MAX,
+                usize => usize::MAX,
+            );
+        }
+
+        #[test]
+        fn test_edge_cases_signed() {
+            assert_edge_cases!(
+                i8 => -1,
+                i16 => -1,
+                i32 => -1,
+                i64 => -1,
+                i128 => -1,
+                isize => -1,
+            );
+        }
+
+        #[test]
+        fn test_edge_cases_high_bit() {
+            // only the sign bit set, which the signed bridge must carry over
+            assert_eq!(i16::MIN.to_big_endian_bytes(), [0x80, 0x00]);
+            assert_eq!(i16::MIN.to_little_endian_bytes(), [0x00, 0x80]);
+            assert_eq!(
+                i32::from_bytes(&[0x80, 0, 0, 0], EndiannessType::BigEndian),
+                Ok(i32::MIN)
+            );
+            assert_eq!(
+                i64::from_bytes(&[0, 0, 0, 0, 0, 0, 0, 0x80], EndiannessType::LittleEndian),
+                Ok(i64::MIN)
+            );
+            assert_eq!(i128::MAX.to_big_endian_bytes()[..2], [0x7f, 0xff]);
+            assert_eq!(
+                u128::from_bytes(&[0x80; 16], EndiannessType::LittleEndian),
+                Ok(u128::from_ne_bytes([0x80; 16]))
+            );
+        }
+    }
 }
@@ -0,0 +1,160 @@
+//! c style network byte order aliases
+//!
+//! network byte order is big endian, so these are thin wrappers around the
+//! big endian [`Endianness`] conversions for code ported from c. the ip
+//! address helpers convert between `core::net` addresses and their wire form.
+
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::Endianness;
+
+/// convert a `u16` from host to network byte order
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{htons, ntohs};
+///
+/// assert_eq!(htons(80).to_ne_bytes(), [0x00, 0x50]);
+/// assert_eq!(ntohs(htons(80)), 80);
+/// ```
+#[inline]
+pub fn htons(value: u16) -> u16 {
+    value.host_to_big_endian()
+}
+
+/// convert a `u32` from host to network byte order
+#[inline]
+pub fn htonl(value: u32) -> u32 {
+    value.host_to_big_endian()
+}
+
+/// convert a `u64` from host to network byte order
+#[inline]
+pub fn htonll(value: u64) -> u64 {
+    value.host_to_big_endian()
+}
+
+/// convert a `u16` from network to host byte order
+#[inline]
+pub fn ntohs(value: u16) -> u16 {
+    value.big_endian_to_host()
+}
+
+/// convert a `u32` from network to host byte order
+#[inline]
+pub fn ntohl(value: u32) -> u32 {
+    value.big_endian_to_host()
+}
+
+/// convert a `u64` from network to host byte order
+#[inline]
+pub fn ntohll(value: u64) -> u64 {
+    value.big_endian_to_host()
+}
+
+/// convert an ipv4 address to a `u32` in network byte order
+///
+/// the in-memory bytes of the result are the octets of the address, ready to
+/// be copied into a packet.
+///
+/// # examples
+///
+/// ```
+/// use core::net::Ipv4Addr;
+/// use endbyte::{ipv4_from_be, ipv4_to_be};
+///
+/// let addr = Ipv4Addr::new(192, 168, 0, 1);
+/// assert_eq!(ipv4_to_be(addr).to_ne_bytes(), [192, 168, 0, 1]);
+/// assert_eq!(ipv4_from_be(ipv4_to_be(addr)), addr);
+/// ```
+#[inline]
+pub fn ipv4_to_be(addr: Ipv4Addr) -> u32 {
+    u32::from(addr).host_to_big_endian()
+}
+
+/// convert a `u32` in network byte order to an ipv4 address
+#[inline]
+pub fn ipv4_from_be(value: u32) -> Ipv4Addr {
+    Ipv4Addr::from(value.big_endian_to_host())
+}
+
+/// convert an ipv6 address to a `u128` in network byte order
+///
+/// the in-memory bytes of the result are the octets of the address.
+#[inline]
+pub fn ipv6_to_be(addr: Ipv6Addr) -> u128 {
+    u128::from(addr).host_to_big_endian()
+}
+
+/// convert a `u128` in network byte order to an ipv6 address
+#[inline]
+pub fn ipv6_from_be(value: u128) -> Ipv6Addr {
+    Ipv6Addr::from(value.big_endian_to_host())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aliases_match_trait() {
+        assert_eq!(htons(0x1234), 0x1234u16.host_to_big_endian());
+        assert_eq!(htonl(0x12345678), 0x12345678u32.host_to_big_endian());
+        assert_eq!(
+            htonll(0x0102030405060708),
+            0x0102030405060708u64.host_to_big_endian()
+        );
+
+        assert_eq!(ntohs(0x1234), 0x1234u16.big_endian_to_host());
+        assert_eq!(ntohl(0x12345678), 0x12345678u32.big_endian_to_host());
+        assert_eq!(
+            ntohll(0x0102030405060708),
+            0x0102030405060708u64.big_endian_to_host()
+        );
+    }
+
+    #[test]
+    fn test_network_byte_layout() {
+        // the port and address of 192.168.0.1:8080 as they appear on the wire
+        assert_eq!(htons(8080).to_ne_bytes(), [0x1f, 0x90]);
+        assert_eq!(htonl(0xc0a8_0001).to_ne_bytes(), [192, 168, 0, 1]);
+        assert_eq!(ntohl(u32::from_ne_bytes([192, 168, 0, 1])), 0xc0a8_0001);
+        assert_eq!(ntohll(htonll(u64::MAX - 1)), u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_ipv4_wire_form() {
+        for addr in [
+            Ipv4Addr::new(192, 168, 0, 1),
+            Ipv4Addr::LOCALHOST,
+            Ipv4Addr::BROADCAST,
+            Ipv4Addr::UNSPECIFIED,
+        ] {
+            let wire = ipv4_to_be(addr);
+            assert_eq!(wire.to_ne_bytes(), addr.octets());
+            assert_eq!(ipv4_from_be(wire), addr);
+            assert_eq!(wire, htonl(u32::from(addr)));
+        }
+
+        assert_eq!(
+            ipv4_from_be(u32::from_ne_bytes([10, 0, 0, 42])),
+            Ipv4Addr::new(10, 0, 0, 42)
+        );
+    }
+
+    #[test]
+    fn test_ipv6_wire_form() {
+        let documentation = Ipv6Addr::new(0x2001, 0x0db8, 0, 0, 0, 0, 0, 1);
+        for addr in [documentation, Ipv6Addr::LOCALHOST, Ipv6Addr::UNSPECIFIED] {
+            let wire = ipv6_to_be(addr);
+            assert_eq!(wire.to_ne_bytes(), addr.octets());
+            assert_eq!(ipv6_from_be(wire), addr);
+        }
+
+        assert_eq!(
+            ipv6_to_be(documentation).to_ne_bytes()[..4],
+            [0x20, 0x01, 0x0d, 0xb8]
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! compile-time byte order markers
+
+use core::marker::PhantomData;
+
+use crate::{EndianError, EndianSerialize, Endianness, EndiannessType};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// a byte order known at compile time
+///
+/// implemented by the [`Be`] and [`Le`] markers only. use it as a type
+/// parameter to write code that is generic over the byte order without a
+/// runtime branch.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{Be, Le, Order};
+///
+/// fn read_length<O: Order>(bytes: &[u8]) -> u16 {
+///     O::read(bytes).unwrap()
+/// }
+///
+/// assert_eq!(read_length::<Be>(&[0x01, 0x00]), 256);
+/// assert_eq!(read_length::<Le>(&[0x01, 0x00]), 1);
+/// ```
+pub trait Order: sealed::Sealed {
+    /// the runtime equivalent of this order
+    const TYPE: EndiannessType;
+
+    /// convert a value from host byte order to this order
+    fn from_host<T: Endianness>(value: T) -> T;
+
+    /// convert a value from this order to host byte order
+    fn to_host<T: Endianness>(value: T) -> T;
+
+    /// read a value stored in this order from the front of `bytes`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if `bytes` is too short.
+    fn read<T: EndianSerialize>(bytes: &[u8]) -> Result<T, EndianError> {
+        T::from_bytes(bytes, Self::TYPE)
+    }
+}
+
+/// big endian byte order marker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Be;
+
+/// little endian byte order marker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Le;
+
+impl sealed::Sealed for Be {}
+impl sealed::Sealed for Le {}
+
+impl Order for Be {
+    const TYPE: EndiannessType = EndiannessType::BigEndian;
+
+    #[inline]
+    fn from_host<T: Endianness>(value: T) -> T {
+        value.host_to_big_endian()
+    }
+
+    #[inline]
+    fn to_host<T: Endianness>(value: T) -> T {
+        value.big_endian_to_host()
+    }
+}
+
+impl Order for Le {
+    const TYPE: EndiannessType = EndiannessType::LittleEndian;
+
+    #[inline]
+    fn from_host<T: Endianness>(value: T) -> T {
+        value.host_to_little_endian()
+    }
+
+    #[inline]
+    fn to_host<T: Endianness>(value: T) -> T {
+        value.little_endian_to_host()
+    }
+}
+
+/// the byte order of the host, resolved at compile time to [`Be`] or [`Le`]
+#[cfg(target_endian = "big")]
+pub type NativeOrder = Be;
+
+/// the byte order of the host, resolved at compile time to [`Be`] or [`Le`]
+#[cfg(target_endian = "little")]
+pub type NativeOrder = Le;
+
+/// a value of type `T` stored in byte order `O`
+///
+/// the order is part of the type, so a big endian and a little endian value
+/// cannot be mixed up, and the raw value can only be used as a host value
+/// after an explicit [`to_host`](Endian::to_host):
+///
+/// ```compile_fail
+/// use endbyte::{Be, Endian, Le};
+///
+/// let big: Endian<u32, Be> = Endian::from_host(1);
+/// let little: Endian<u32, Le> = big;
+/// ```
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{Be, Endian};
+///
+/// let length = Endian::<u32, Be>::from_host(0x0102);
+/// assert_eq!(length.raw().to_ne_bytes(), [0x00, 0x00, 0x01, 0x02]);
+/// assert_eq!(length.to_host(), 0x0102);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct Endian<T, O: Order> {
+    raw: T,
+    order: PhantomData<O>,
+}
+
+impl<T: Endianness, O: Order> Endian<T, O> {
+    /// convert a host order value into order `O`
+    #[inline]
+    pub fn from_host(value: T) -> Self {
+        Self::from_raw(O::from_host(value))
+    }
+
+    /// convert the stored value back to host byte order
+    #[inline]
+    pub fn to_host(self) -> T {
+        O::to_host(self.raw)
+    }
+}
+
+impl<T, O: Order> Endian<T, O> {
+    /// wrap a raw value that is already stored in order `O`
+    #[inline]
+    pub const fn from_raw(raw: T) -> Self {
+        Self {
+            raw,
+            order: PhantomData,
+        }
+    }
+
+    /// return the raw value in order `O`
+    #[inline]
+    pub fn raw(self) -> T {
+        self.raw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_order_matches_host() {
+        let bytes = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
+
+        assert_eq!(
+            NativeOrder::read::<u32>(&bytes),
+            Ok(u32::from_ne_bytes([0x12, 0x34, 0x56, 0x78]))
+        );
+        assert_eq!(
+            NativeOrder::read::<u64>(&bytes),
+            Ok(u64::from_ne_bytes(bytes))
+        );
+        assert_eq!(NativeOrder::TYPE, crate::get_local_endianness());
+
+        // converting to the native order never changes the value
+        assert_eq!(NativeOrder::from_host(0x1234u16), 0x1234);
+        assert_eq!(NativeOrder::to_host(-5i64), -5);
+    }
+
+    #[test]
+    fn test_order_markers() {
+        assert_eq!(Be::read::<u16>(&[0x12, 0x34]), Ok(0x1234));
+        assert_eq!(Le::read::<u16>(&[0x12, 0x34]), Ok(0x3412));
+
+        let value = 0x12345678u32;
+        assert_eq!(Be::from_host(value), value.host_to_big_endian());
+        assert_eq!(Le::from_host(value), value.host_to_little_endian());
+        assert_eq!(Be::to_host(Be::from_host(value)), value);
+        assert_eq!(Le::to_host(Le::from_host(value)), value);
+    }
+
+    #[test]
+    fn test_endian_round_trip() {
+        let big = Endian::<u32, Be>::from_host(0x12345678);
+        let little = Endian::<u32, Le>::from_host(0x12345678);
+
+        assert_eq!(big.raw().to_ne_bytes(), [0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(little.raw().to_ne_bytes(), [0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(big.to_host(), 0x12345678);
+        assert_eq!(little.to_host(), 0x12345678);
+
+        let signed = Endian::<i16, Be>::from_host(-2);
+        assert_eq!(signed.raw().to_ne_bytes(), [0xff, 0xfe]);
+        assert_eq!(signed.to_host(), -2);
+    }
+
+    #[test]
+    fn test_endian_from_raw() {
+        let raw = u16::from_ne_bytes([0x01, 0x02]);
+
+        assert_eq!(Endian::<u16, Be>::from_raw(raw).to_host(), 0x0102);
+        assert_eq!(Endian::<u16, Le>::from_raw(raw).to_host(), 0x0201);
+        assert_eq!(Endian::<u16, NativeOrder>::from_raw(raw).to_host(), raw);
+        assert_eq!(
+            core::mem::size_of::<Endian<u64, Be>>(),
+            core::mem::size_of::<u64>()
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! middle endian (pdp-11) byte order for 32-bit words
+//!
+//! the pdp-11 stores a 32-bit word as two 16-bit halves with the high half
+//! first, each half little endian. numbering the bytes of the big endian
+//! form `1 2 3 4`, from most to least significant, the stored order is
+//! `2 1 4 3`:
+//!
+//! | value        | big endian    | pdp-11        |
+//! |--------------|---------------|---------------|
+//! | `0x0a0b0c0d` | `0a 0b 0c 0d` | `0b 0a 0d 0c` |
+
+use crate::Endianness;
+
+/// conversions between host byte order and pdp-11 middle endian order
+///
+/// like the [`Endianness`] conversions, a converted value holds the stored
+/// bytes in memory order, so `to_ne_bytes` returns the bytes as stored.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::MiddleEndian;
+///
+/// let stored = 0x0a0b0c0du32.host_to_pdp_endian();
+/// assert_eq!(stored.to_ne_bytes(), [0x0b, 0x0a, 0x0d, 0x0c]);
+/// assert_eq!(stored.pdp_endian_to_host(), 0x0a0b0c0d);
+/// ```
+pub trait MiddleEndian {
+    /// convert from host byte order to pdp-11 middle endian
+    fn host_to_pdp_endian(self) -> Self;
+
+    /// convert from pdp-11 middle endian to host byte order
+    fn pdp_endian_to_host(self) -> Self;
+}
+
+// swap the two bytes of each 16-bit lane, which swaps memory bytes 0 and 1
+// and bytes 2 and 3 on hosts of either byte order
+#[inline]
+const fn swap_halves_bytes(value: u32) -> u32 {
+    ((value & 0x00ff_00ff) << 8) | ((value >> 8) & 0x00ff_00ff)
+}
+
+impl MiddleEndian for u32 {
+    #[inline]
+    fn host_to_pdp_endian(self) -> Self {
+        swap_halves_bytes(self.host_to_big_endian())
+    }
+
+    #[inline]
+    fn pdp_endian_to_host(self) -> Self {
+        swap_halves_bytes(self).big_endian_to_host()
+    }
+}
+
+impl MiddleEndian for i32 {
+    #[inline]
+    fn host_to_pdp_endian(self) -> Self {
+        (self as u32).host_to_pdp_endian() as i32
+    }
+
+    #[inline]
+    fn pdp_endian_to_host(self) -> Self {
+        (self as u32).pdp_endian_to_host() as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdp_permutation() {
+        // bytes 1 2 3 4 of the big endian form are stored as 2 1 4 3
+        let value = 0x0102_0304u32;
+        assert_eq!(
+            value.host_to_pdp_endian().to_ne_bytes(),
+            [0x02, 0x01, 0x04, 0x03]
+        );
+        assert_eq!(
+            u32::from_ne_bytes([0x02, 0x01, 0x04, 0x03]).pdp_endian_to_host(),
+            value
+        );
+
+        // 1.0 as a pdp-11 single precision float, 0x40800000, is stored as 80 40 00 00
+        assert_eq!(
+            0x4080_0000u32.host_to_pdp_endian().to_ne_bytes(),
+            [0x80, 0x40, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_pdp_round_trip() {
+        for value in [0u32, 1, 0xdead_beef, u32::MAX] {
+            assert_eq!(value.host_to_pdp_endian().pdp_endian_to_host(), value);
+        }
+
+        let signed = -2i32;
+        assert_eq!(
+            signed.host_to_pdp_endian().to_ne_bytes(),
+            [0xff, 0xff, 0xfe, 0xff]
+        );
+        assert_eq!(signed.host_to_pdp_endian().pdp_endian_to_host(), signed);
+    }
+}
@@ -0,0 +1,1029 @@
+//! free functions reading integers from the front of a byte slice
+
+use crate::{EndianError, EndianSerialize, Endianness, EndiannessType};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// copy the first `N` bytes of `bytes` into an array
+#[inline]
+pub(crate) fn take<const N: usize>(bytes: &[u8]) -> Result<[u8; N], EndianError> {
+    bytes
+        .get(..N)
+        .and_then(|head| head.try_into().ok())
+        .ok_or(EndianError::UnexpectedEof {
+            needed: N,
+            available: bytes.len(),
+        })
+}
+
+/// read the first four bytes of `bytes` as both a big endian and a little
+/// endian `u32`
+///
+/// returns `(big_endian, little_endian)`. useful for diagnostics and fuzzing,
+/// where the same bytes should be shown under both interpretations.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 4 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_u32_both;
+///
+/// let (be, le) = read_u32_both(&[0x12, 0x34, 0x56, 0x78]).unwrap();
+/// assert_eq!(be, 0x12345678);
+/// assert_eq!(le, 0x78563412);
+/// ```
+pub fn read_u32_both(bytes: &[u8]) -> Result<(u32, u32), EndianError> {
+    let raw = u32::from_ne_bytes(take(bytes)?);
+    Ok((raw.big_endian_to_host(), raw.little_endian_to_host()))
+}
+
+/// read a big endian 24-bit signed integer, sign extended to `i32`
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 3 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_i24_be;
+///
+/// assert_eq!(read_i24_be(&[0xff, 0xff, 0xfe]).unwrap(), -2);
+/// ```
+pub fn read_i24_be(bytes: &[u8]) -> Result<i32, EndianError> {
+    let [b0, b1, b2] = take(bytes)?;
+
+    // place the 24 bits at the top of an i32 so the shift sign extends them
+    Ok(i32::from_ne_bytes([b0, b1, b2, 0]).big_endian_to_host() >> 8)
+}
+
+/// read a big endian 24-bit signed sample and narrow it to 16 bits
+///
+/// the low 8 bits are dropped. with `round` unset the value is truncated
+/// towards negative infinity (an arithmetic shift). with `round` set it is
+/// rounded to the nearest 16-bit value, with halfway cases rounded up, and
+/// saturated at `i16::MAX` where rounding up would overflow.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 3 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_i24_be_as_i16;
+///
+/// assert_eq!(read_i24_be_as_i16(&[0x12, 0x34, 0x80], false).unwrap(), 0x1234);
+/// assert_eq!(read_i24_be_as_i16(&[0x12, 0x34, 0x80], true).unwrap(), 0x1235);
+/// ```
+pub fn read_i24_be_as_i16(bytes: &[u8], round: bool) -> Result<i16, EndianError> {
+    let sample = read_i24_be(bytes)?;
+    let narrowed = if round {
+        (sample + 0x80) >> 8
+    } else {
+        sample >> 8
+    };
+
+    Ok(narrowed.min(i16::MAX as i32) as i16)
+}
+
+/// read a big endian `i16` and sign extend it to `i32`
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 2 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_i16_be_as_i32;
+///
+/// assert_eq!(read_i16_be_as_i32(&[0xff, 0xfe]).unwrap(), -2);
+/// ```
+pub fn read_i16_be_as_i32(bytes: &[u8]) -> Result<i32, EndianError> {
+    i16::from_bytes(bytes, EndiannessType::BigEndian).map(i32::from)
+}
+
+/// read a big endian `i32` and narrow it to `i16`
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 4 bytes and
+/// [`EndianError::Overflow`] if the value is outside the `i16` range.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{read_i32_be_as_i16_checked, EndianError};
+///
+/// assert_eq!(read_i32_be_as_i16_checked(&[0xff, 0xff, 0x80, 0x00]), Ok(i16::MIN));
+/// assert_eq!(read_i32_be_as_i16_checked(&[0x00, 0x00, 0x80, 0x00]), Err(EndianError::Overflow));
+/// ```
+pub fn read_i32_be_as_i16_checked(bytes: &[u8]) -> Result<i16, EndianError> {
+    let value = i32::from_bytes(bytes, EndiannessType::BigEndian)?;
+    i16::try_from(value).map_err(|_| EndianError::Overflow)
+}
+
+/// read a big endian `u16` and extract a right aligned bitfield from it
+///
+/// the field is `width` bits wide and starts `shift` bits above the least
+/// significant bit of the host order value.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 2 bytes and
+/// [`EndianError::InvalidWidth`] if the field is empty or does not fit in 16
+/// bits.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_bits_u16_be;
+///
+/// // a 4-bit tag above a 12-bit length
+/// assert_eq!(read_bits_u16_be(&[0xab, 0xcd], 12, 4), Ok(0xa));
+/// assert_eq!(read_bits_u16_be(&[0xab, 0xcd], 0, 12), Ok(0xbcd));
+/// ```
+pub fn read_bits_u16_be(bytes: &[u8], shift: u32, width: u32) -> Result<u16, EndianError> {
+    if width == 0 || shift.saturating_add(width) > 16 {
+        return Err(EndianError::InvalidWidth(width as usize));
+    }
+
+    let word = u16::from_bytes(bytes, EndiannessType::BigEndian)?;
+    let mask = (u32::MAX >> (32 - width)) as u16;
+    Ok((word >> shift) & mask)
+}
+
+/// read a `u64` stored as two big endian `u32` halves
+///
+/// with `hi_first` set the high half comes first, otherwise the low half does.
+/// each half is always big endian.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 8 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_u64_split_be;
+///
+/// let bytes = [0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02];
+/// assert_eq!(read_u64_split_be(&bytes, true), Ok(0x0000000100000002));
+/// assert_eq!(read_u64_split_be(&bytes, false), Ok(0x0000000200000001));
+/// ```
+pub fn read_u64_split_be(bytes: &[u8], hi_first: bool) -> Result<u64, EndianError> {
+    let [a0, a1, a2, a3, b0, b1, b2, b3] = take(bytes)?;
+    let first = u32::from_ne_bytes([a0, a1, a2, a3]).big_endian_to_host();
+    let second = u32::from_ne_bytes([b0, b1, b2, b3]).big_endian_to_host();
+    let (hi, lo) = if hi_first {
+        (first, second)
+    } else {
+        (second, first)
+    };
+
+    Ok((u64::from(hi) << 32) | u64::from(lo))
+}
+
+/// read a pascal string prefixed by a single length byte
+///
+/// returns the string and the number of bytes consumed, including the length
+/// byte. the string bytes must be valid utf-8, which covers the ascii subset of
+/// latin-1.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if the declared length exceeds the
+/// buffer and [`EndianError::InvalidUtf8`] if the bytes are not valid utf-8.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_pascal_string;
+///
+/// assert_eq!(read_pascal_string(b"\x05hello world"), Ok(("hello", 6)));
+/// ```
+pub fn read_pascal_string(bytes: &[u8]) -> Result<(&str, usize), EndianError> {
+    let (&len, rest) = bytes.split_first().ok_or(EndianError::UnexpectedEof {
+        needed: 1,
+        available: 0,
+    })?;
+
+    let len = usize::from(len);
+    let text = rest.get(..len).ok_or(EndianError::UnexpectedEof {
+        needed: len + 1,
+        available: bytes.len(),
+    })?;
+
+    let text = core::str::from_utf8(text).map_err(|_| EndianError::InvalidUtf8)?;
+    Ok((text, len + 1))
+}
+
+/// read a big endian `u32` from the front of `input` and advance past it
+///
+/// on success `*input` is resliced to start after the consumed bytes. on error
+/// it is left unchanged.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `input` is shorter than 4 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_u32_be_advance;
+///
+/// let mut input = &[0x00, 0x00, 0x00, 0x2a, 0xff][..];
+/// assert_eq!(read_u32_be_advance(&mut input), Ok(42));
+/// assert_eq!(input, [0xff]);
+/// ```
+pub fn read_u32_be_advance(input: &mut &[u8]) -> Result<u32, EndianError> {
+    let value = u32::from_bytes(input, EndiannessType::BigEndian)?;
+    *input = &input[4..];
+    Ok(value)
+}
+
+/// read a big endian value from the front of `buf` and return it with the rest
+///
+/// the width comes from [`EndianSerialize::BYTES`], so one function covers
+/// every type, and threading the returned slice through suits hand-written
+/// recursive descent parsers.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `buf` is shorter than the value.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_be;
+///
+/// let (kind, rest) = read_be::<u16>(&[0x00, 0x07, 0x2a]).unwrap();
+/// let (flags, rest) = read_be::<u8>(rest).unwrap();
+/// assert_eq!((kind, flags, rest.len()), (7, 42, 0));
+/// ```
+pub fn read_be<T: EndianSerialize>(buf: &[u8]) -> Result<(T, &[u8]), EndianError> {
+    let value = T::from_bytes(buf, EndiannessType::BigEndian)?;
+    Ok((value, &buf[T::BYTES..]))
+}
+
+/// read a little endian value from the front of `buf` and return it with the rest
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `buf` is shorter than the value.
+pub fn read_le<T: EndianSerialize>(buf: &[u8]) -> Result<(T, &[u8]), EndianError> {
+    let value = T::from_bytes(buf, EndiannessType::LittleEndian)?;
+    Ok((value, &buf[T::BYTES..]))
+}
+
+/// read a big endian `u32` at `offset` in `a` followed by `b`
+///
+/// the two buffers are treated as one logical buffer, so a value straddling
+/// the end of `a` is read without copying the buffers together first.
+///
+/// # errors
+///
+/// returns [`EndianError::OffsetOutOfBounds`] if `offset` is past the end of
+/// both buffers and [`EndianError::UnexpectedEof`] if fewer than 4 bytes
+/// follow it.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_u32_be_split;
+///
+/// assert_eq!(read_u32_be_split(&[0xff, 0x12, 0x34], &[0x56, 0x78], 1), Ok(0x12345678));
+/// ```
+pub fn read_u32_be_split(a: &[u8], b: &[u8], offset: usize) -> Result<u32, EndianError> {
+    let len = a.len() + b.len();
+    if offset > len {
+        return Err(EndianError::OffsetOutOfBounds { offset, len });
+    }
+    if len - offset < 4 {
+        return Err(EndianError::UnexpectedEof {
+            needed: 4,
+            available: len - offset,
+        });
+    }
+
+    let mut bytes = [0u8; 4];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        let index = offset + i;
+        *byte = if index < a.len() {
+            a[index]
+        } else {
+            b[index - a.len()]
+        };
+    }
+
+    u32::from_bytes(&bytes, EndiannessType::BigEndian)
+}
+
+/// read a big endian signed fixed-point value and convert it to `f64`
+///
+/// the value is a two's complement integer of `int_bits + frac_bits` bits,
+/// where `int_bits` includes the sign bit, scaled by `2^-frac_bits`. the total
+/// must be 8, 16, 32 or 64 bits. 64-bit values with more than 53 significant
+/// bits are rounded to the nearest `f64`.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidBits`] with the total bit count if it is not
+/// a supported width, and [`EndianError::UnexpectedEof`] if `bytes` is too
+/// short.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_fixed_be;
+///
+/// // q8.8: 0x0180 is 1.5
+/// assert_eq!(read_fixed_be(&[0x01, 0x80], 8, 8), Ok(1.5));
+/// ```
+pub fn read_fixed_be(bytes: &[u8], int_bits: u32, frac_bits: u32) -> Result<f64, EndianError> {
+    let order = EndiannessType::BigEndian;
+    let total = int_bits.saturating_add(frac_bits);
+
+    let raw = match total {
+        8 => f64::from(i8::from_bytes(bytes, order)?),
+        16 => f64::from(i16::from_bytes(bytes, order)?),
+        32 => f64::from(i32::from_bytes(bytes, order)?),
+        64 => i64::from_bytes(bytes, order)? as f64,
+        _ => return Err(EndianError::InvalidBits(total)),
+    };
+
+    // frac_bits is at most 64 here, so the scale fits in a u128
+    Ok(raw / (1u128 << frac_bits) as f64)
+}
+
+/// read a nul terminated string from the front of `bytes`
+///
+/// returns the bytes before the nul and the number of bytes consumed,
+/// including the nul, so fields that follow start at that offset.
+///
+/// # errors
+///
+/// returns [`EndianError::MissingNul`] if `bytes` contains no nul.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_cstr;
+///
+/// assert_eq!(read_cstr(b"name\0\x00\x2a"), Ok((&b"name"[..], 5)));
+/// ```
+pub fn read_cstr(bytes: &[u8]) -> Result<(&[u8], usize), EndianError> {
+    let len = bytes
+        .iter()
+        .position(|&byte| byte == 0)
+        .ok_or(EndianError::MissingNul)?;
+
+    Ok((&bytes[..len], len + 1))
+}
+
+/// read a big endian `u32` at `offset` in a const context
+///
+/// meant for decoding constant tables at compile time. out of bounds reads
+/// panic, which in a const context is a compile error.
+///
+/// # panics
+///
+/// panics if `buf` holds fewer than 4 bytes after `offset`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_u32_be_const;
+///
+/// const X: u32 = read_u32_be_const(&[0, 0, 1, 0], 0);
+/// assert_eq!(X, 256);
+/// ```
+pub const fn read_u32_be_const(buf: &[u8], offset: usize) -> u32 {
+    let raw = u32::from_ne_bytes([
+        buf[offset],
+        buf[offset + 1],
+        buf[offset + 2],
+        buf[offset + 3],
+    ]);
+    crate::u32_from_be(raw)
+}
+
+/// read a table of `count` big endian `u32` offsets from the front of `bytes`
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` holds fewer than `count`
+/// offsets.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_offset_table_u32_be;
+///
+/// let table = read_offset_table_u32_be(&[0, 0, 0, 8, 0, 0, 0, 12], 2).unwrap();
+/// assert_eq!(table, [8, 12]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn read_offset_table_u32_be(bytes: &[u8], count: usize) -> Result<Vec<u32>, EndianError> {
+    let needed = count.saturating_mul(4);
+    let table = bytes.get(..needed).ok_or(EndianError::UnexpectedEof {
+        needed,
+        available: bytes.len(),
+    })?;
+
+    Ok(table
+        .chunks_exact(4)
+        .map(|chunk| {
+            u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]).big_endian_to_host()
+        })
+        .collect())
+}
+
+/// resolve each offset against `bytes`, returning the data starting at it
+///
+/// every returned slice runs from its offset to the end of `bytes`, ready to be
+/// decoded with the other readers. this is the second step after
+/// [`read_offset_table_u32_be`].
+///
+/// # errors
+///
+/// returns [`EndianError::OffsetOutOfBounds`] for the first offset that does not
+/// point inside `bytes`.
+#[cfg(feature = "alloc")]
+pub fn read_at_offsets<'a>(bytes: &'a [u8], offsets: &[u32]) -> Result<Vec<&'a [u8]>, EndianError> {
+    offsets
+        .iter()
+        .map(|&offset| {
+            let offset = offset as usize;
+            if offset < bytes.len() {
+                Ok(&bytes[offset..])
+            } else {
+                Err(EndianError::OffsetOutOfBounds {
+                    offset,
+                    len: bytes.len(),
+                })
+            }
+        })
+        .collect()
+}
+
+/// read a big endian `u32` count followed by that many big endian records
+///
+/// every record is decoded with [`EndianSerialize::from_bytes`], so `T` can be
+/// any type implementing [`EndianSerialize`]. returns the records in order.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than the count
+/// or than the `count * T::BYTES` bytes of records following it, and
+/// [`EndianError::InvalidWidth`] if `T` is zero sized, since the count could
+/// then not be checked against the buffer.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_prefixed_array;
+///
+/// let bytes = [0, 0, 0, 2, 0x12, 0x34, 0xff, 0xfe];
+/// assert_eq!(read_prefixed_array::<i16>(&bytes), Ok(vec![0x1234, -2]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn read_prefixed_array<T: EndianSerialize>(bytes: &[u8]) -> Result<Vec<T>, EndianError> {
+    // zero sized records would let an untrusted count of up to u32::MAX
+    // through the length check below
+    if T::BYTES == 0 {
+        return Err(EndianError::InvalidWidth(0));
+    }
+
+    let count = u32::from_bytes(bytes, EndiannessType::BigEndian)? as usize;
+    let records = &bytes[4..];
+
+    // check the whole length up front so a bogus count cannot allocate
+    let needed = count.saturating_mul(T::BYTES);
+    if records.len() < needed {
+        return Err(EndianError::UnexpectedEof {
+            needed,
+            available: records.len(),
+        });
+    }
+
+    (0..count)
+        .map(|i| T::from_bytes(&records[i * T::BYTES..], EndiannessType::BigEndian))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_u32_both() {
+        let (be, le) = read_u32_both(&[0x12, 0x34, 0x56, 0x78, 0xff]).unwrap();
+        assert_eq!(be, 0x12345678);
+        assert_eq!(le, 0x78563412);
+
+        // the two interpretations always differ by a byte swap
+        assert_eq!(be, le.swap_bytes());
+    }
+
+    #[test]
+    fn test_read_u32_both_palindrome() {
+        let (be, le) = read_u32_both(&[0xab, 0xcd, 0xcd, 0xab]).unwrap();
+        assert_eq!(be, le);
+    }
+
+    #[test]
+    fn test_read_u32_both_short_buffer() {
+        assert_eq!(
+            read_u32_both(&[0x12, 0x34]),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_i24_be() {
+        assert_eq!(read_i24_be(&[0x12, 0x34, 0x56]).unwrap(), 0x123456);
+        assert_eq!(read_i24_be(&[0x7f, 0xff, 0xff]).unwrap(), 0x7fffff);
+        assert_eq!(read_i24_be(&[0x80, 0x00, 0x00]).unwrap(), -0x800000);
+        assert_eq!(read_i24_be(&[0xff, 0xff, 0xff]).unwrap(), -1);
+        assert_eq!(
+            read_i24_be(&[0x12, 0x34]),
+            Err(EndianError::UnexpectedEof {
+                needed: 3,
+                available: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_i24_be_as_i16_rounds_up() {
+        // 0x1234.80 is exactly halfway and rounds up
+        assert_eq!(
+            read_i24_be_as_i16(&[0x12, 0x34, 0x80], true).unwrap(),
+            0x1235
+        );
+        assert_eq!(
+            read_i24_be_as_i16(&[0x12, 0x34, 0x80], false).unwrap(),
+            0x1234
+        );
+
+        // rounding near the top saturates instead of overflowing
+        assert_eq!(
+            read_i24_be_as_i16(&[0x7f, 0xff, 0xff], true).unwrap(),
+            i16::MAX
+        );
+    }
+
+    #[test]
+    fn test_read_i24_be_as_i16_truncates() {
+        // 0x1234.7f is below halfway so both modes agree
+        assert_eq!(
+            read_i24_be_as_i16(&[0x12, 0x34, 0x7f], true).unwrap(),
+            0x1234
+        );
+        assert_eq!(
+            read_i24_be_as_i16(&[0x12, 0x34, 0x7f], false).unwrap(),
+            0x1234
+        );
+
+        // truncation of negative values moves towards negative infinity
+        assert_eq!(read_i24_be_as_i16(&[0xff, 0xff, 0x80], false).unwrap(), -1);
+        assert_eq!(read_i24_be_as_i16(&[0xff, 0xff, 0x80], true).unwrap(), 0);
+        assert_eq!(
+            read_i24_be_as_i16(&[0x80, 0x00, 0x00], true).unwrap(),
+            i16::MIN
+        );
+    }
+
+    #[test]
+    fn test_read_i16_be_as_i32() {
+        assert_eq!(read_i16_be_as_i32(&[0x12, 0x34]).unwrap(), 0x1234);
+        assert_eq!(read_i16_be_as_i32(&[0xff, 0xff]).unwrap(), -1);
+        assert_eq!(read_i16_be_as_i32(&[0x80, 0x00]).unwrap(), -32768);
+        assert_eq!(read_i16_be_as_i32(&[0xfe, 0xd4]).unwrap(), -300);
+    }
+
+    #[test]
+    fn test_read_i32_be_as_i16_checked() {
+        assert_eq!(
+            read_i32_be_as_i16_checked(&[0xff, 0xff, 0xfe, 0xd4]),
+            Ok(-300)
+        );
+        assert_eq!(
+            read_i32_be_as_i16_checked(&[0x00, 0x00, 0x7f, 0xff]),
+            Ok(i16::MAX)
+        );
+        assert_eq!(
+            read_i32_be_as_i16_checked(&[0xff, 0xff, 0x80, 0x00]),
+            Ok(i16::MIN)
+        );
+
+        // one past either end of the i16 range overflows
+        assert_eq!(
+            read_i32_be_as_i16_checked(&[0x00, 0x00, 0x80, 0x00]),
+            Err(EndianError::Overflow)
+        );
+        assert_eq!(
+            read_i32_be_as_i16_checked(&[0xff, 0xff, 0x7f, 0xff]),
+            Err(EndianError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_read_bits_u16_be() {
+        let word = [0xab, 0xcd];
+
+        assert_eq!(read_bits_u16_be(&word, 0, 12), Ok(0xbcd));
+        assert_eq!(read_bits_u16_be(&word, 12, 4), Ok(0xa));
+        assert_eq!(read_bits_u16_be(&word, 4, 8), Ok(0xbc));
+        assert_eq!(read_bits_u16_be(&word, 0, 16), Ok(0xabcd));
+        assert_eq!(read_bits_u16_be(&word, 15, 1), Ok(1));
+    }
+
+    #[test]
+    fn test_read_bits_u16_be_errors() {
+        assert_eq!(
+            read_bits_u16_be(&[0xab, 0xcd], 8, 9),
+            Err(EndianError::InvalidWidth(9))
+        );
+        assert_eq!(
+            read_bits_u16_be(&[0xab, 0xcd], 0, 0),
+            Err(EndianError::InvalidWidth(0))
+        );
+        assert_eq!(
+            read_bits_u16_be(&[0xab], 0, 4),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_u64_split_be() {
+        let value = 0x0123456789abcdefu64;
+        let hi_first = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+        let lo_first = [0x89, 0xab, 0xcd, 0xef, 0x01, 0x23, 0x45, 0x67];
+
+        assert_eq!(read_u64_split_be(&hi_first, true), Ok(value));
+        assert_eq!(read_u64_split_be(&lo_first, false), Ok(value));
+
+        // hi-first with both halves big endian is a plain big endian u64
+        assert_eq!(
+            read_u64_split_be(&hi_first, true),
+            u64::from_bytes(&hi_first, EndiannessType::BigEndian)
+        );
+        assert_eq!(
+            read_u64_split_be(&lo_first[..7], false),
+            Err(EndianError::UnexpectedEof {
+                needed: 8,
+                available: 7
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_pascal_string() {
+        assert_eq!(read_pascal_string(b"\x03abcdef"), Ok(("abc", 4)));
+        assert_eq!(read_pascal_string(b"\x00"), Ok(("", 1)));
+        assert_eq!(
+            read_pascal_string("\x05caf\u{e9}".as_bytes()),
+            Ok(("caf\u{e9}", 6))
+        );
+    }
+
+    #[test]
+    fn test_read_pascal_string_errors() {
+        assert_eq!(
+            read_pascal_string(b"\x0ashort"),
+            Err(EndianError::UnexpectedEof {
+                needed: 11,
+                available: 6
+            })
+        );
+        assert_eq!(
+            read_pascal_string(b""),
+            Err(EndianError::UnexpectedEof {
+                needed: 1,
+                available: 0
+            })
+        );
+        assert_eq!(
+            read_pascal_string(b"\x01\xff"),
+            Err(EndianError::InvalidUtf8)
+        );
+    }
+
+    #[test]
+    fn test_read_u32_be_advance() {
+        let bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0xaa, 0xbb];
+        let mut input = &bytes[..];
+
+        assert_eq!(read_u32_be_advance(&mut input), Ok(1));
+        assert_eq!(read_u32_be_advance(&mut input), Ok(2));
+        assert_eq!(read_u32_be_advance(&mut input), Ok(3));
+        assert_eq!(input, [0xaa, 0xbb]);
+
+        // a failed read leaves the input untouched
+        assert_eq!(
+            read_u32_be_advance(&mut input),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 2
+            })
+        );
+        assert_eq!(input, [0xaa, 0xbb]);
+    }
+
+    #[test]
+    fn test_read_fixed_be_q8_8() {
+        assert_eq!(read_fixed_be(&[0x01, 0x80], 8, 8), Ok(1.5));
+        assert_eq!(read_fixed_be(&[0xff, 0x00], 8, 8), Ok(-1.0));
+        assert_eq!(read_fixed_be(&[0x80, 0x00], 8, 8), Ok(-128.0));
+        assert_eq!(read_fixed_be(&[0x7f, 0xff], 8, 8), Ok(127.99609375));
+    }
+
+    #[test]
+    fn test_read_fixed_be_q4_12() {
+        assert_eq!(read_fixed_be(&[0x10, 0x00], 4, 12), Ok(1.0));
+        assert_eq!(read_fixed_be(&[0x08, 0x00], 4, 12), Ok(0.5));
+        assert_eq!(read_fixed_be(&[0xe0, 0x00], 4, 12), Ok(-2.0));
+        assert_eq!(read_fixed_be(&[0x00, 0x01], 4, 12), Ok(1.0 / 4096.0));
+    }
+
+    #[test]
+    fn test_read_fixed_be_other_widths() {
+        assert_eq!(read_fixed_be(&[0x00, 0x01, 0x80, 0x00], 16, 16), Ok(1.5));
+        assert_eq!(read_fixed_be(&[0xf0], 4, 4), Ok(-1.0));
+        assert_eq!(
+            read_fixed_be(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02], 63, 1),
+            Ok(1.0)
+        );
+    }
+
+    #[test]
+    fn test_read_fixed_be_errors() {
+        assert_eq!(
+            read_fixed_be(&[0; 4], 4, 8),
+            Err(EndianError::InvalidBits(12))
+        );
+        assert_eq!(
+            read_fixed_be(&[0; 4], 16, 32),
+            Err(EndianError::InvalidBits(48))
+        );
+        assert_eq!(
+            read_fixed_be(&[0x01], 8, 8),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_cstr() {
+        let record = b"sensor\0\x00\x00\x01\x00";
+
+        let (name, consumed) = read_cstr(record).unwrap();
+        assert_eq!(name, b"sensor");
+        assert_eq!(consumed, 7);
+        assert_eq!(
+            u32::from_bytes(&record[consumed..], EndiannessType::BigEndian),
+            Ok(256)
+        );
+
+        assert_eq!(read_cstr(b"\0"), Ok((&b""[..], 1)));
+    }
+
+    #[test]
+    fn test_read_cstr_unterminated() {
+        assert_eq!(read_cstr(b"sensor"), Err(EndianError::MissingNul));
+        assert_eq!(read_cstr(b""), Err(EndianError::MissingNul));
+    }
+
+    #[test]
+    fn test_read_u32_be_const() {
+        const TABLE: [u8; 8] = [0x00, 0x00, 0x01, 0x00, 0xde, 0xad, 0xbe, 0xef];
+        const FIRST: u32 = read_u32_be_const(&TABLE, 0);
+        const SECOND: u32 = read_u32_be_const(&TABLE, 4);
+
+        assert_eq!(FIRST, 256);
+        assert_eq!(SECOND, 0xdeadbeef);
+        assert_eq!(read_u32_be_const(&TABLE, 2), 0x0100dead);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_read_u32_be_const_out_of_bounds() {
+        read_u32_be_const(&[0, 0, 0, 0], 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_read_offset_table() {
+        let mut buf = [0u8; 24];
+        buf[..12].copy_from_slice(&[0, 0, 0, 12, 0, 0, 0, 16, 0, 0, 0, 20]);
+        buf[12..16].copy_from_slice(&[0xaa, 0xbb, 0xcc, 0xdd]);
+        buf[20] = 0x42;
+
+        let offsets = read_offset_table_u32_be(&buf, 3).unwrap();
+        assert_eq!(offsets, [12, 16, 20]);
+
+        let entries = read_at_offsets(&buf, &offsets).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(read_u32_both(entries[0]).unwrap().0, 0xaabbccdd);
+        assert_eq!(entries[2], [0x42, 0, 0, 0]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_read_offset_table_errors() {
+        let buf = [0, 0, 0, 4, 0, 0, 0, 64];
+
+        assert_eq!(
+            read_offset_table_u32_be(&buf, 3),
+            Err(EndianError::UnexpectedEof {
+                needed: 12,
+                available: 8
+            })
+        );
+
+        let offsets = read_offset_table_u32_be(&buf, 2).unwrap();
+        assert_eq!(
+            read_at_offsets(&buf, &offsets),
+            Err(EndianError::OffsetOutOfBounds { offset: 64, len: 8 })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_read_prefixed_array() {
+        let bytes = [
+            0x00, 0x00, 0x00, 0x03, // count
+            0x00, 0x00, 0x00, 0x01, //
+            0x00, 0x01, 0x00, 0x00, //
+            0xff, 0xff, 0xff, 0xff, //
+            0xaa, // trailing byte is not consumed
+        ];
+
+        assert_eq!(
+            read_prefixed_array::<u32>(&bytes),
+            Ok(alloc::vec![1, 0x10000, u32::MAX])
+        );
+        assert_eq!(read_prefixed_array::<u8>(&[0, 0, 0, 0]), Ok(alloc::vec![]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_read_prefixed_array_truncated() {
+        let bytes = [0x00, 0x00, 0x00, 0x03, 0x00, 0x01, 0x00, 0x02, 0x00];
+
+        assert_eq!(
+            read_prefixed_array::<u16>(&bytes),
+            Err(EndianError::UnexpectedEof {
+                needed: 6,
+                available: 5
+            })
+        );
+        assert_eq!(
+            read_prefixed_array::<u16>(&bytes[..3]),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 3
+            })
+        );
+
+        // a huge count fails without allocating
+        assert!(read_prefixed_array::<u64>(&[0xff; 8]).is_err());
+    }
+
+    #[test]
+    fn test_read_u32_be_split() {
+        let a = [0x00, 0x11, 0x22];
+        let b = [0x33, 0x44, 0x55];
+
+        // straddling the boundary at every split point
+        assert_eq!(read_u32_be_split(&a, &b, 0), Ok(0x00112233));
+        assert_eq!(read_u32_be_split(&a, &b, 1), Ok(0x11223344));
+        assert_eq!(read_u32_be_split(&a, &b, 2), Ok(0x22334455));
+
+        // entirely within one buffer
+        assert_eq!(read_u32_be_split(&[1, 2, 3, 4], &[], 0), Ok(0x01020304));
+        assert_eq!(read_u32_be_split(&[], &[1, 2, 3, 4], 0), Ok(0x01020304));
+        assert_eq!(read_u32_be_split(&[9], &[1, 2, 3, 4], 1), Ok(0x01020304));
+    }
+
+    #[test]
+    fn test_read_u32_be_split_errors() {
+        let a = [0x00, 0x11, 0x22];
+        let b = [0x33, 0x44, 0x55];
+
+        assert_eq!(
+            read_u32_be_split(&a, &b, 3),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 3
+            })
+        );
+        assert_eq!(
+            read_u32_be_split(&a, &b, 7),
+            Err(EndianError::OffsetOutOfBounds { offset: 7, len: 6 })
+        );
+    }
+
+    #[test]
+    fn test_read_be_mixed_widths() {
+        let bytes = [
+            0x7f, // u8
+            0xff, 0xfe, // i16
+            0x12, 0x34, 0x56, 0x78, // u32
+            0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // i64
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // u128
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, //
+            0xaa, // left over
+        ];
+
+        let (a, rest) = read_be::<u8>(&bytes).unwrap();
+        let (b, rest) = read_be::<i16>(rest).unwrap();
+        let (c, rest) = read_be::<u32>(rest).unwrap();
+        let (d, rest) = read_be::<i64>(rest).unwrap();
+        let (e, rest) = read_be::<u128>(rest).unwrap();
+
+        assert_eq!((a, b, c, d, e), (0x7f, -2, 0x12345678, i64::MIN, 1));
+        assert_eq!(rest, [0xaa]);
+    }
+
+    #[test]
+    fn test_read_le_and_errors() {
+        let (value, rest) = read_le::<u32>(&[0x78, 0x56, 0x34, 0x12]).unwrap();
+        assert_eq!(value, 0x12345678);
+        assert!(rest.is_empty());
+
+        let (value, rest) = read_le::<i8>(&[0xff, 0x01]).unwrap();
+        assert_eq!((value, rest), (-1, &[0x01][..]));
+
+        assert_eq!(
+            read_be::<u64>(&[0; 7]),
+            Err(EndianError::UnexpectedEof {
+                needed: 8,
+                available: 7
+            })
+        );
+        assert!(read_le::<u16>(&[]).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_read_prefixed_array_zero_sized() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Empty;
+
+        impl Endianness for Empty {
+            fn host_to_big_endian(self) -> Self {
+                self
+            }
+
+            fn host_to_little_endian(self) -> Self {
+                self
+            }
+
+            fn big_endian_to_host(self) -> Self {
+                self
+            }
+
+            fn little_endian_to_host(self) -> Self {
+                self
+            }
+        }
+
+        impl EndianSerialize for Empty {
+            const BYTES: usize = 0;
+
+            fn from_bytes(_bytes: &[u8], _order: EndiannessType) -> Result<Self, EndianError> {
+                Ok(Empty)
+            }
+
+            fn write_bytes(
+                self,
+                _buf: &mut [u8],
+                _order: EndiannessType,
+            ) -> Result<usize, EndianError> {
+                Ok(0)
+            }
+        }
+
+        // a huge count of zero sized records is rejected instead of looped over
+        assert_eq!(
+            read_prefixed_array::<Empty>(&[0xff, 0xff, 0xff, 0xff]),
+            Err(EndianError::InvalidWidth(0))
+        );
+    }
+}
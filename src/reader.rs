@@ -0,0 +1,673 @@
+//! cursor for reading endian values from a byte slice
+
+use core::fmt;
+
+use crate::{EndianError, EndianSerialize, EndiannessType};
+
+/// a value read by an [`EndianReader`] together with where it came from
+///
+/// the display form names the field, for use in validation errors:
+///
+/// ```
+/// use endbyte::EndianReader;
+///
+/// let mut reader = EndianReader::new(&[0x00, 0x00, 0x00, 0x2a]);
+/// let flags = reader.read_u32_be_named("flags").unwrap();
+/// assert_eq!(flags.to_string(), "field `flags` at offset 0 = 0x2a");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Named<T> {
+    /// name of the field
+    pub name: &'static str,
+    /// offset of the field from the start of the buffer
+    pub offset: usize,
+    /// value read from the field
+    pub value: T,
+}
+
+impl<T: fmt::LowerHex> fmt::Display for Named<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "field `{}` at offset {} = {:#x}",
+            self.name, self.offset, self.value
+        )
+    }
+}
+
+// generate the big and little endian read methods for each type
+macro_rules! impl_read_methods {
+    ($($t:ty, $bytes:literal => $be:ident, $le:ident;)*) => {
+        $(
+            #[doc = concat!("read a big endian `", stringify!($t), "`")]
+            ///
+            /// # errors
+            ///
+            #[doc = concat!(
+                "returns [`EndianError::UnexpectedEof`] if fewer than ",
+                stringify!($bytes),
+                " bytes remain."
+            )]
+            pub fn $be(&mut self) -> Result<$t, EndianError> {
+                self.read(EndiannessType::BigEndian)
+            }
+
+            #[doc = concat!("read a little endian `", stringify!($t), "`")]
+            ///
+            /// # errors
+            ///
+            #[doc = concat!(
+                "returns [`EndianError::UnexpectedEof`] if fewer than ",
+                stringify!($bytes),
+                " bytes remain."
+            )]
+            pub fn $le(&mut self) -> Result<$t, EndianError> {
+                self.read(EndiannessType::LittleEndian)
+            }
+        )*
+    };
+}
+
+/// cursor reading endian values from the front of a byte slice
+///
+/// every successful read advances the position past the consumed bytes. a
+/// failed read leaves the position unchanged.
+///
+/// a reader created with [`new_lenient`](EndianReader::new_lenient) repairs
+/// common mistakes instead of failing: length prefixes running past the end
+/// of the buffer are clamped and trailing bytes are ignored. each repair is
+/// counted in [`repairs`](EndianReader::repairs).
+///
+/// # examples
+///
+/// ```
+/// use endbyte::EndianReader;
+///
+/// let mut reader = EndianReader::new(&[0x12, 0x34, 0x56, 0x78, 0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(reader.read_u32_be(), Ok(0x12345678));
+/// assert_eq!(reader.expect_terminator_u32_be(0xdeadbeef), Ok(()));
+/// assert_eq!(reader.remaining(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EndianReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    lenient: bool,
+    repairs: usize,
+}
+
+impl<'a> EndianReader<'a> {
+    /// create a reader positioned at the start of `bytes`
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            lenient: false,
+            repairs: 0,
+        }
+    }
+
+    /// create a lenient reader positioned at the start of `bytes`
+    pub const fn new_lenient(bytes: &'a [u8]) -> Self {
+        Self {
+            lenient: true,
+            ..Self::new(bytes)
+        }
+    }
+
+    /// whether malformed input is repaired instead of rejected
+    pub const fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// number of malformed fields a lenient reader has repaired so far
+    pub const fn repairs(&self) -> usize {
+        self.repairs
+    }
+
+    /// current offset from the start of the buffer
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// number of bytes left to read
+    pub const fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    /// read a value stored in `order`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if too few bytes remain.
+    pub fn read<T: EndianSerialize>(&mut self, order: EndiannessType) -> Result<T, EndianError> {
+        let value = T::from_bytes(&self.bytes[self.position..], order)?;
+        self.position += T::BYTES;
+        Ok(value)
+    }
+
+    /// read a `u8`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if no bytes remain.
+    pub fn read_u8(&mut self) -> Result<u8, EndianError> {
+        self.read(EndiannessType::BigEndian)
+    }
+
+    /// read an `i8`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if no bytes remain.
+    pub fn read_i8(&mut self) -> Result<i8, EndianError> {
+        self.read(EndiannessType::BigEndian)
+    }
+
+    impl_read_methods! {
+        u16, 2 => read_u16_be, read_u16_le;
+        u32, 4 => read_u32_be, read_u32_le;
+        u64, 8 => read_u64_be, read_u64_le;
+        u128, 16 => read_u128_be, read_u128_le;
+        i16, 2 => read_i16_be, read_i16_le;
+        i32, 4 => read_i32_be, read_i32_le;
+        i64, 8 => read_i64_be, read_i64_le;
+        i128, 16 => read_i128_be, read_i128_le;
+    }
+
+    /// read a slice prefixed by its length as a big endian `u16`
+    ///
+    /// a lenient reader clamps a length that runs past the end of the buffer
+    /// and returns the bytes that are there.
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if the prefix is missing or, in
+    /// strict mode, if fewer bytes than the prefix declares remain.
+    ///
+    /// # examples
+    ///
+    /// ```
+    /// use endbyte::EndianReader;
+    ///
+    /// let mut reader = EndianReader::new(&[0x00, 0x02, b'o', b'k']);
+    /// assert_eq!(reader.read_prefixed_u16_be(), Ok(&b"ok"[..]));
+    /// ```
+    pub fn read_prefixed_u16_be(&mut self) -> Result<&'a [u8], EndianError> {
+        let start = self.position;
+        let len = usize::from(self.read_u16_be()?);
+
+        let available = self.remaining();
+        let len = if len <= available {
+            len
+        } else if self.lenient {
+            self.repairs += 1;
+            available
+        } else {
+            self.position = start;
+            return Err(EndianError::UnexpectedEof {
+                needed: 2 + len,
+                available: 2 + available,
+            });
+        };
+
+        let data = &self.bytes[self.position..self.position + len];
+        self.position += len;
+        Ok(data)
+    }
+
+    /// check that every byte has been read
+    ///
+    /// a lenient reader ignores trailing bytes.
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::TrailingBytes`] with the number of unread bytes
+    /// if a strict reader has not reached the end of the buffer.
+    pub fn finish(&mut self) -> Result<(), EndianError> {
+        match self.remaining() {
+            0 => Ok(()),
+            _ if self.lenient => {
+                self.repairs += 1;
+                Ok(())
+            }
+            len => Err(EndianError::TrailingBytes(len)),
+        }
+    }
+
+    /// read a big endian `u32` and tag it with the field `name` and its offset
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if fewer than 4 bytes remain.
+    pub fn read_u32_be_named(&mut self, name: &'static str) -> Result<Named<u32>, EndianError> {
+        let offset = self.position;
+        let value = self.read_u32_be()?;
+        Ok(Named {
+            name,
+            offset,
+            value,
+        })
+    }
+
+    /// read a big endian `u32` terminator and check that it equals `sentinel`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if fewer than 4 bytes remain and
+    /// [`EndianError::UnexpectedValue`] if the word differs from `sentinel`.
+    pub fn expect_terminator_u32_be(&mut self, sentinel: u32) -> Result<(), EndianError> {
+        let offset = self.position;
+        let found = self.read_u32_be()?;
+
+        if found != sentinel {
+            self.position = offset;
+            return Err(EndianError::UnexpectedValue {
+                offset,
+                expected: sentinel.into(),
+                found: found.into(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// read a big endian `u32` reserved field and check that it is zero
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if fewer than 4 bytes remain and
+    /// [`EndianError::ReservedNonZero`] with the offset and value of the field
+    /// if it is nonzero. the position is left unchanged on error.
+    ///
+    /// # examples
+    ///
+    /// ```
+    /// use endbyte::EndianReader;
+    ///
+    /// let mut reader = EndianReader::new(&[0x00, 0x00, 0x00, 0x00, 0x2a]);
+    /// assert_eq!(reader.expect_zero_u32_be(), Ok(()));
+    /// assert_eq!(reader.read_u8(), Ok(42));
+    /// ```
+    pub fn expect_zero_u32_be(&mut self) -> Result<(), EndianError> {
+        let offset = self.position;
+        let value = self.read_u32_be()?;
+
+        if value != 0 {
+            self.position = offset;
+            return Err(EndianError::ReservedNonZero { offset, value });
+        }
+
+        Ok(())
+    }
+
+    /// read a big endian `u16` version and check that it is within `min..=max`
+    ///
+    /// returns the version if it is supported.
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if fewer than 2 bytes remain and
+    /// [`EndianError::UnsupportedVersion`] if the version is out of range.
+    pub fn expect_version_u16_be(&mut self, min: u16, max: u16) -> Result<u16, EndianError> {
+        let offset = self.position;
+        let found = self.read_u16_be()?;
+
+        if !(min..=max).contains(&found) {
+            self.position = offset;
+            return Err(EndianError::UnsupportedVersion { found, min, max });
+        }
+
+        Ok(found)
+    }
+
+    /// skip pad bytes up to the next multiple of `boundary`
+    ///
+    /// the boundary is measured from the start of the buffer. returns the
+    /// number of pad bytes skipped, which is zero if the position is already
+    /// aligned. with `strict` set, every pad byte must be zero.
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if the buffer ends before the
+    /// boundary and, in strict mode, [`EndianError::UnexpectedValue`] for the
+    /// first nonzero pad byte.
+    ///
+    /// # panics
+    ///
+    /// panics if `boundary` is zero.
+    ///
+    /// # examples
+    ///
+    /// ```
+    /// use endbyte::EndianReader;
+    ///
+    /// let mut reader = EndianReader::new(&[0x00, 0x2a, 0x00, 0x00, 0x00, 0x07]);
+    /// assert_eq!(reader.read_u16_be(), Ok(42));
+    /// assert_eq!(reader.consume_padding(4, true), Ok(2));
+    /// assert_eq!(reader.read_u16_be(), Ok(7));
+    /// ```
+    pub fn consume_padding(&mut self, boundary: usize, strict: bool) -> Result<usize, EndianError> {
+        let padding = self.position.next_multiple_of(boundary) - self.position;
+        let pad = self
+            .bytes
+            .get(self.position..self.position + padding)
+            .ok_or(EndianError::UnexpectedEof {
+                needed: padding,
+                available: self.remaining(),
+            })?;
+
+        if strict {
+            if let Some(index) = pad.iter().position(|&byte| byte != 0) {
+                return Err(EndianError::UnexpectedValue {
+                    offset: self.position + index,
+                    expected: 0,
+                    found: pad[index].into(),
+                });
+            }
+        }
+
+        self.position += padding;
+        Ok(padding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reader_advances() {
+        let mut reader = EndianReader::new(&[0x00, 0x00, 0x00, 0x01, 0x02, 0x03]);
+
+        assert_eq!(reader.read_u32_be(), Ok(1));
+        assert_eq!(reader.position(), 4);
+        assert_eq!(reader.remaining(), 2);
+
+        // a failed read does not move the cursor
+        assert_eq!(
+            reader.read_u32_be(),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 2
+            })
+        );
+        assert_eq!(reader.position(), 4);
+        assert_eq!(reader.read::<u16>(EndiannessType::LittleEndian), Ok(0x0302));
+    }
+
+    #[test]
+    fn test_parse_sequence() {
+        let header = [
+            0x7f, // version
+            0xfe, // offset
+            0x12, 0x34, // id, big endian
+            0x34, 0x12, // id, little endian
+            0xff, 0xff, 0xff, 0xfe, // delta, big endian
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // size, little endian
+            0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // min, big endian
+        ];
+        let mut reader = EndianReader::new(&header);
+
+        assert_eq!(reader.read_u8(), Ok(0x7f));
+        assert_eq!(reader.read_i8(), Ok(-2));
+        assert_eq!(reader.read_u16_be(), Ok(0x1234));
+        assert_eq!(reader.read_i16_le(), Ok(0x1234));
+        assert_eq!(reader.read_i32_be(), Ok(-2));
+        assert_eq!(reader.position(), 10);
+        assert_eq!(reader.read_u64_le(), Ok(1));
+        assert_eq!(reader.read_i64_be(), Ok(i64::MIN));
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn test_wide_reads() {
+        let bytes = [0xab; 16];
+        let mut reader = EndianReader::new(&bytes);
+        assert_eq!(reader.read_u128_le(), Ok(u128::from_ne_bytes(bytes)));
+
+        let mut reader = EndianReader::new(&bytes);
+        assert_eq!(reader.read_i128_be(), Ok(i128::from_ne_bytes(bytes)));
+        assert_eq!(EndianReader::new(&bytes[..4]).read_u32_le(), Ok(0xabababab));
+    }
+
+    #[test]
+    fn test_short_buffer() {
+        let mut reader = EndianReader::new(&[0x01, 0x02, 0x03]);
+
+        assert_eq!(
+            reader.read_u64_be(),
+            Err(EndianError::UnexpectedEof {
+                needed: 8,
+                available: 3
+            })
+        );
+        assert_eq!(reader.read_u16_le(), Ok(0x0201));
+        assert_eq!(
+            reader.read_i16_be(),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+        assert_eq!(reader.read_u8(), Ok(0x03));
+        assert_eq!(
+            reader.read_i8(),
+            Err(EndianError::UnexpectedEof {
+                needed: 1,
+                available: 0
+            })
+        );
+        assert_eq!(reader.position(), 3);
+    }
+
+    #[test]
+    fn test_read_named() {
+        let mut reader = EndianReader::new(&[0, 0, 0, 1, 0, 0, 0, 4, 0xff]);
+
+        let magic = reader.read_u32_be_named("magic").unwrap();
+        let flags = reader.read_u32_be_named("flags").unwrap();
+        assert_eq!(
+            magic,
+            Named {
+                name: "magic",
+                offset: 0,
+                value: 1
+            }
+        );
+        assert_eq!(flags.name, "flags");
+        assert_eq!(flags.offset, 4);
+        assert_eq!(flags.value, 4);
+
+        assert!(reader.read_u32_be_named("length").is_err());
+        assert_eq!(reader.position(), 8);
+    }
+
+    #[test]
+    fn test_expect_terminator() {
+        let record = [0x00, 0x2a, 0xde, 0xad, 0xbe, 0xef];
+        let mut reader = EndianReader::new(&record);
+
+        assert_eq!(reader.read::<u16>(EndiannessType::BigEndian), Ok(42));
+        assert_eq!(reader.expect_terminator_u32_be(0xdeadbeef), Ok(()));
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn test_expect_terminator_corrupted() {
+        let record = [0x00, 0x2a, 0xde, 0xad, 0xbe, 0xee];
+        let mut reader = EndianReader::new(&record);
+        reader.read::<u16>(EndiannessType::BigEndian).unwrap();
+
+        assert_eq!(
+            reader.expect_terminator_u32_be(0xdeadbeef),
+            Err(EndianError::UnexpectedValue {
+                offset: 2,
+                expected: 0xdeadbeef,
+                found: 0xdeadbeee
+            })
+        );
+        assert_eq!(reader.position(), 2);
+
+        // a missing terminator is reported as running out of bytes
+        let mut reader = EndianReader::new(&record[..4]);
+        reader.read::<u16>(EndiannessType::BigEndian).unwrap();
+        assert_eq!(
+            reader.expect_terminator_u32_be(0xdeadbeef),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_expect_zero() {
+        let header = [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00];
+        let mut reader = EndianReader::new(&header);
+
+        assert_eq!(reader.read_u8(), Ok(1));
+        assert_eq!(reader.expect_zero_u32_be(), Ok(()));
+        assert_eq!(reader.position(), 5);
+
+        // a nonzero reserved field reports its offset and value
+        assert_eq!(
+            reader.expect_zero_u32_be(),
+            Err(EndianError::ReservedNonZero {
+                offset: 5,
+                value: 0x8000
+            })
+        );
+        assert_eq!(reader.position(), 5);
+    }
+
+    #[test]
+    fn test_read_prefixed_u16_be() {
+        let mut reader = EndianReader::new(&[0x00, 0x02, 0xaa, 0xbb, 0x00, 0x00]);
+
+        assert_eq!(reader.read_prefixed_u16_be(), Ok(&[0xaa, 0xbb][..]));
+        assert_eq!(reader.read_prefixed_u16_be(), Ok(&[][..]));
+        assert_eq!(reader.finish(), Ok(()));
+        assert_eq!(reader.repairs(), 0);
+    }
+
+    #[test]
+    fn test_strict_and_lenient_modes() {
+        // a prefix declaring one byte too many, and a stray byte after the
+        // last field
+        let short = [0x00, 0x04, b'a', b'b', b'c'];
+        let trailing = [0x00, 0x01, b'a', 0xff];
+
+        let mut strict = EndianReader::new(&short);
+        assert!(!strict.is_lenient());
+        assert_eq!(
+            strict.read_prefixed_u16_be(),
+            Err(EndianError::UnexpectedEof {
+                needed: 6,
+                available: 5
+            })
+        );
+        assert_eq!(strict.position(), 0);
+
+        let mut lenient = EndianReader::new_lenient(&short);
+        assert!(lenient.is_lenient());
+        assert_eq!(lenient.read_prefixed_u16_be(), Ok(&b"abc"[..]));
+        assert_eq!(lenient.finish(), Ok(()));
+        assert_eq!(lenient.repairs(), 1);
+
+        let mut strict = EndianReader::new(&trailing);
+        assert_eq!(strict.read_prefixed_u16_be(), Ok(&b"a"[..]));
+        assert_eq!(strict.finish(), Err(EndianError::TrailingBytes(1)));
+
+        let mut lenient = EndianReader::new_lenient(&trailing);
+        assert_eq!(lenient.read_prefixed_u16_be(), Ok(&b"a"[..]));
+        assert_eq!(lenient.finish(), Ok(()));
+        assert_eq!(lenient.repairs(), 1);
+    }
+
+    #[test]
+    fn test_expect_version() {
+        let mut reader = EndianReader::new(&[0x00, 0x02, 0xff]);
+        assert_eq!(reader.expect_version_u16_be(1, 3), Ok(2));
+        assert_eq!(reader.position(), 2);
+
+        // the bounds are inclusive
+        assert_eq!(
+            EndianReader::new(&[0, 1]).expect_version_u16_be(1, 3),
+            Ok(1)
+        );
+        assert_eq!(
+            EndianReader::new(&[0, 3]).expect_version_u16_be(1, 3),
+            Ok(3)
+        );
+    }
+
+    #[test]
+    fn test_expect_version_out_of_range() {
+        let mut too_old = EndianReader::new(&[0x00, 0x01]);
+        assert_eq!(
+            too_old.expect_version_u16_be(2, 4),
+            Err(EndianError::UnsupportedVersion {
+                found: 1,
+                min: 2,
+                max: 4
+            })
+        );
+        assert_eq!(too_old.position(), 0);
+
+        let mut too_new = EndianReader::new(&[0x01, 0x00]);
+        assert_eq!(
+            too_new.expect_version_u16_be(2, 4),
+            Err(EndianError::UnsupportedVersion {
+                found: 256,
+                min: 2,
+                max: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_consume_padding_zero() {
+        let mut reader = EndianReader::new(&[0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02]);
+
+        // already aligned
+        assert_eq!(reader.consume_padding(4, true), Ok(0));
+        assert_eq!(reader.read_u16_be(), Ok(1));
+        assert_eq!(reader.consume_padding(4, true), Ok(2));
+        assert_eq!(reader.position(), 4);
+        assert_eq!(reader.read_u32_be(), Ok(2));
+
+        // a boundary past the end of the buffer is an error
+        let mut reader = EndianReader::new(&[0x00, 0x01, 0x00]);
+        reader.read_u16_be().unwrap();
+        assert_eq!(
+            reader.consume_padding(4, false),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+        assert_eq!(reader.position(), 2);
+    }
+
+    #[test]
+    fn test_consume_padding_nonzero() {
+        let record = [0x00, 0x01, 0x00, 0xcc];
+
+        let mut reader = EndianReader::new(&record);
+        reader.read_u16_be().unwrap();
+        assert_eq!(
+            reader.consume_padding(4, true),
+            Err(EndianError::UnexpectedValue {
+                offset: 3,
+                expected: 0,
+                found: 0xcc
+            })
+        );
+        assert_eq!(reader.position(), 2);
+
+        // non-strict padding skips the garbage
+        assert_eq!(reader.consume_padding(4, false), Ok(2));
+        assert_eq!(reader.remaining(), 0);
+    }
+}
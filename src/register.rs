@@ -0,0 +1,132 @@
+//! access to big endian device register windows
+
+use crate::{EndianError, EndianSerialize, EndiannessType};
+
+/// big endian register window backed by a byte slice
+///
+/// registers are addressed by their byte offset into the window and are
+/// converted between the device's big endian order and host order on every
+/// access. this is the plain memory version: it does not use volatile reads or
+/// writes, so it suits register dumps and emulated devices rather than live
+/// mmio.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::RegisterWindow;
+///
+/// let mut regs = [0u8; 8];
+/// let mut window = RegisterWindow::new(&mut regs);
+/// window.write_u32(4, 0x8000_0001).unwrap();
+/// assert_eq!(window.read_u32(4), Ok(0x8000_0001));
+/// assert_eq!(regs[4..], [0x80, 0x00, 0x00, 0x01]);
+/// ```
+#[derive(Debug)]
+pub struct RegisterWindow<'a> {
+    regs: &'a mut [u8],
+}
+
+impl<'a> RegisterWindow<'a> {
+    /// create a window over `regs`, with offset `0` at the first byte
+    pub fn new(regs: &'a mut [u8]) -> Self {
+        Self { regs }
+    }
+
+    /// size of the window in bytes
+    pub fn len(&self) -> usize {
+        self.regs.len()
+    }
+
+    /// returns `true` if the window holds no registers
+    pub fn is_empty(&self) -> bool {
+        self.regs.is_empty()
+    }
+
+    // byte range of the 4 byte register at `offset`, if it lies inside the window
+    fn register(&self, offset: usize) -> Result<core::ops::Range<usize>, EndianError> {
+        match offset.checked_add(4) {
+            Some(end) if end <= self.regs.len() => Ok(offset..end),
+            _ => Err(EndianError::OffsetOutOfBounds {
+                offset,
+                len: self.regs.len(),
+            }),
+        }
+    }
+
+    /// read the big endian `u32` register at `offset`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::OffsetOutOfBounds`] if the register does not lie
+    /// entirely inside the window.
+    pub fn read_u32(&self, offset: usize) -> Result<u32, EndianError> {
+        u32::from_bytes(
+            &self.regs[self.register(offset)?],
+            EndiannessType::BigEndian,
+        )
+    }
+
+    /// write `value` to the big endian `u32` register at `offset`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::OffsetOutOfBounds`] if the register does not lie
+    /// entirely inside the window.
+    pub fn write_u32(&mut self, offset: usize, value: u32) -> Result<(), EndianError> {
+        let register = self.register(offset)?;
+        value.write_bytes(&mut self.regs[register], EndiannessType::BigEndian)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_window_read() {
+        let mut regs = [
+            0x00, 0x00, 0x00, 0x00, // control
+            0x12, 0x34, 0x56, 0x78, // status
+            0xde, 0xad, 0xbe, 0xef, // id
+        ];
+        let window = RegisterWindow::new(&mut regs);
+
+        assert_eq!(window.len(), 12);
+        assert_eq!(window.read_u32(4), Ok(0x12345678));
+        assert_eq!(window.read_u32(8), Ok(0xdeadbeef));
+
+        // registers may straddle word boundaries
+        assert_eq!(window.read_u32(6), Ok(0x5678dead));
+    }
+
+    #[test]
+    fn test_register_window_write() {
+        let mut regs = [0u8; 8];
+        let mut window = RegisterWindow::new(&mut regs);
+
+        window.write_u32(0, 0x0000_00ff).unwrap();
+        window.write_u32(4, 0x0102_0304).unwrap();
+        assert_eq!(window.read_u32(0), Ok(0xff));
+        assert_eq!(regs, [0x00, 0x00, 0x00, 0xff, 0x01, 0x02, 0x03, 0x04]);
+    }
+
+    #[test]
+    fn test_register_window_bounds() {
+        let mut regs = [0u8; 8];
+        let mut window = RegisterWindow::new(&mut regs);
+
+        assert_eq!(
+            window.read_u32(5),
+            Err(EndianError::OffsetOutOfBounds { offset: 5, len: 8 })
+        );
+        assert_eq!(
+            window.write_u32(usize::MAX, 1),
+            Err(EndianError::OffsetOutOfBounds {
+                offset: usize::MAX,
+                len: 8
+            })
+        );
+        assert_eq!(regs, [0; 8]);
+    }
+}
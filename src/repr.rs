@@ -0,0 +1,606 @@
+//! conversions for non two's complement integer representations
+
+use crate::{EndianError, EndianSerialize, Endianness, EndiannessType};
+
+/// read a big endian sign-magnitude `i32` and convert it to two's complement
+///
+/// the most significant bit holds the sign and the remaining 31 bits hold the
+/// magnitude, so both `0x00000000` and `0x80000000` decode to `0`.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 4 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_i32_be_sign_magnitude;
+///
+/// assert_eq!(read_i32_be_sign_magnitude(&[0x80, 0x00, 0x00, 0x05]), Ok(-5));
+/// ```
+pub fn read_i32_be_sign_magnitude(bytes: &[u8]) -> Result<i32, EndianError> {
+    let raw = u32::from_bytes(bytes, EndiannessType::BigEndian)?;
+    let magnitude = (raw & 0x7fff_ffff) as i32;
+
+    if raw & 0x8000_0000 != 0 {
+        Ok(-magnitude)
+    } else {
+        Ok(magnitude)
+    }
+}
+
+/// read a big endian one's complement `i32` and convert it to two's complement
+///
+/// negative values are stored as the bitwise inverse of their magnitude, so
+/// both `0x00000000` and `0xffffffff` decode to `0`.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than 4 bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_i32_be_ones_complement;
+///
+/// assert_eq!(read_i32_be_ones_complement(&[0xff, 0xff, 0xff, 0xfa]), Ok(-5));
+/// ```
+pub fn read_i32_be_ones_complement(bytes: &[u8]) -> Result<i32, EndianError> {
+    let raw = u32::from_bytes(bytes, EndiannessType::BigEndian)? as i32;
+
+    // a negative one's complement value is one below its two's complement value
+    if raw < 0 {
+        Ok(raw + 1)
+    } else {
+        Ok(raw)
+    }
+}
+
+/// decode a big endian group varint of four `u32` values
+///
+/// the first byte holds four 2-bit length codes, most significant bits first,
+/// each storing the byte length of one value minus one. the values follow as
+/// big endian integers of 1 to 4 bytes. returns the values and the total
+/// number of bytes consumed, including the prefix.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` is shorter than the group.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_group_varint_be;
+///
+/// // lengths 1, 2, 1, 1 -> prefix 0b00_01_00_00
+/// let group = [0x10, 0x05, 0x01, 0x00, 0x07, 0x08];
+/// assert_eq!(read_group_varint_be(&group), Ok(([5, 256, 7, 8], 6)));
+/// ```
+pub fn read_group_varint_be(bytes: &[u8]) -> Result<([u32; 4], usize), EndianError> {
+    let (&prefix, mut rest) = bytes.split_first().ok_or(EndianError::UnexpectedEof {
+        needed: 1,
+        available: 0,
+    })?;
+
+    let lengths: [usize; 4] =
+        core::array::from_fn(|i| ((prefix >> (6 - 2 * i)) & 0b11) as usize + 1);
+    let needed = 1 + lengths.iter().sum::<usize>();
+    if bytes.len() < needed {
+        return Err(EndianError::UnexpectedEof {
+            needed,
+            available: bytes.len(),
+        });
+    }
+
+    let mut values = [0u32; 4];
+    for (value, len) in values.iter_mut().zip(lengths) {
+        // right align the value in a zeroed big endian word
+        let mut word = [0u8; 4];
+        word[4 - len..].copy_from_slice(&rest[..len]);
+        *value = u32::from_ne_bytes(word).big_endian_to_host();
+        rest = &rest[len..];
+    }
+
+    Ok((values, needed))
+}
+
+/// decode a big endian variable length integer
+///
+/// each byte carries 7 bits of the value, most significant group first, and
+/// its high bit is set when more bytes follow. this is the scheme used by midi
+/// and asn.1 object identifiers. leb128 uses the same continuation bit but
+/// stores the least significant group first, so the two are not
+/// interchangeable: `[0x81, 0x00]` is `128` here and `1` in leb128. returns
+/// the value and the number of bytes consumed.
+///
+/// # errors
+///
+/// returns [`EndianError::UnexpectedEof`] if `bytes` ends before the final byte
+/// and [`EndianError::Overflow`] if the value does not fit in a `u64`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_be_varint;
+///
+/// assert_eq!(read_be_varint(&[0x7f]), Ok((127, 1)));
+/// assert_eq!(read_be_varint(&[0x81, 0x00]), Ok((128, 2)));
+/// ```
+pub fn read_be_varint(bytes: &[u8]) -> Result<(u64, usize), EndianError> {
+    let mut value = 0u64;
+
+    for (i, &byte) in bytes.iter().enumerate() {
+        // the next shift would push set bits out of the top
+        if value >> 57 != 0 {
+            return Err(EndianError::Overflow);
+        }
+
+        value = (value << 7) | u64::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+
+    Err(EndianError::UnexpectedEof {
+        needed: bytes.len() + 1,
+        available: bytes.len(),
+    })
+}
+
+/// encode `value` in the fewest big endian bytes that hold it
+///
+/// returns the length and an array whose first `len` bytes are the encoding,
+/// with leading zero bytes dropped. zero is encoded as the single byte `0x00`,
+/// so the length is always between 1 and 8.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::encode_minimal_be;
+///
+/// let (len, bytes) = encode_minimal_be(0x1234);
+/// assert_eq!(bytes[..len], [0x12, 0x34]);
+/// ```
+pub fn encode_minimal_be(value: u64) -> (usize, [u8; 8]) {
+    let len = (8 - value.leading_zeros() as usize / 8).max(1);
+
+    let mut bytes = [0u8; 8];
+    bytes[..len].copy_from_slice(&value.host_to_big_endian().to_ne_bytes()[8 - len..]);
+    (len, bytes)
+}
+
+/// decode a big endian integer written by [`encode_minimal_be`]
+///
+/// any length from 1 to 8 bytes is accepted. leading zero bytes are not
+/// rejected, so a padded encoding decodes to the same value.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidWidth`] if `bytes` is empty or longer than 8
+/// bytes.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::decode_minimal_be;
+///
+/// assert_eq!(decode_minimal_be(&[0x12, 0x34]), Ok(0x1234));
+/// ```
+pub fn decode_minimal_be(bytes: &[u8]) -> Result<u64, EndianError> {
+    if !(1..=8).contains(&bytes.len()) {
+        return Err(EndianError::InvalidWidth(bytes.len()));
+    }
+
+    Ok(bytes
+        .iter()
+        .fold(0u64, |value, &byte| (value << 8) | u64::from(byte)))
+}
+
+/// read a big endian offset binary (excess-K) value of `width` bytes
+///
+/// the field stores `value + bias` as an unsigned integer, so the signed value
+/// is recovered by subtracting `bias`. a bias of half the field range, such as
+/// `0x800` for a 12-bit adc stored in 2 bytes, maps midscale to zero.
+/// `width` must be between 1 and 8.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidWidth`] for an unsupported width,
+/// [`EndianError::UnexpectedEof`] if `bytes` is shorter than `width` and
+/// [`EndianError::Overflow`] if the result does not fit in an `i64`.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::read_offset_binary_be;
+///
+/// assert_eq!(read_offset_binary_be(&[0x80, 0x00], 2, 0x8000), Ok(0));
+/// assert_eq!(read_offset_binary_be(&[0x00, 0x00], 2, 0x8000), Ok(-32768));
+/// ```
+pub fn read_offset_binary_be(bytes: &[u8], width: usize, bias: u64) -> Result<i64, EndianError> {
+    if !(1..=8).contains(&width) {
+        return Err(EndianError::InvalidWidth(width));
+    }
+
+    let field = bytes.get(..width).ok_or(EndianError::UnexpectedEof {
+        needed: width,
+        available: bytes.len(),
+    })?;
+
+    // right align the field in a zeroed big endian word
+    let mut word = [0u8; 8];
+    word[8 - width..].copy_from_slice(field);
+    let raw = u64::from_ne_bytes(word).big_endian_to_host();
+
+    i64::try_from(i128::from(raw) - i128::from(bias)).map_err(|_| EndianError::Overflow)
+}
+
+/// write `value` as a big endian offset binary (excess-K) field of `width` bytes
+///
+/// the inverse of [`read_offset_binary_be`]: stores `value + bias` as an
+/// unsigned integer. returns the number of bytes written.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidWidth`] for an unsupported width,
+/// [`EndianError::Overflow`] if `value + bias` is negative or does not fit in
+/// `width` bytes and [`EndianError::UnexpectedEof`] if `buf` is too short.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::write_offset_binary_be;
+///
+/// let mut buf = [0u8; 2];
+/// assert_eq!(write_offset_binary_be(-1, 2, 0x8000, &mut buf), Ok(2));
+/// assert_eq!(buf, [0x7f, 0xff]);
+/// ```
+pub fn write_offset_binary_be(
+    value: i64,
+    width: usize,
+    bias: u64,
+    buf: &mut [u8],
+) -> Result<usize, EndianError> {
+    if !(1..=8).contains(&width) {
+        return Err(EndianError::InvalidWidth(width));
+    }
+
+    let raw =
+        u64::try_from(i128::from(value) + i128::from(bias)).map_err(|_| EndianError::Overflow)?;
+    if width < 8 && raw >> (8 * width) != 0 {
+        return Err(EndianError::Overflow);
+    }
+
+    let available = buf.len();
+    let field = buf.get_mut(..width).ok_or(EndianError::UnexpectedEof {
+        needed: width,
+        available,
+    })?;
+
+    let bytes = raw.host_to_big_endian().to_ne_bytes();
+    field.copy_from_slice(&bytes[8 - width..]);
+    Ok(width)
+}
+
+/// integers with a canonical byte-wise sortable key
+///
+/// the key is the value widened to 128 bits and stored in big endian, so
+/// comparing keys byte by byte (for example with `memcmp` or in an on-disk
+/// index) gives the same order as comparing the numbers, regardless of the
+/// width they were stored with.
+///
+/// signed values use offset binary: the value is sign extended to `i128` and
+/// its sign bit is flipped, so negative keys sort before positive ones. keys
+/// are ordered across all unsigned types and across all signed types, but
+/// signed and unsigned keys must not be mixed.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::SortKey;
+///
+/// assert!(300u16.to_sort_key() < 70_000u64.to_sort_key());
+/// assert!((-1i8).to_sort_key() < 0i64.to_sort_key());
+/// ```
+pub trait SortKey {
+    /// return the 16 byte big endian sort key of `self`
+    fn to_sort_key(self) -> [u8; 16];
+}
+
+macro_rules! impl_sort_key_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl SortKey for $t {
+                fn to_sort_key(self) -> [u8; 16] {
+                    (self as u128).host_to_big_endian().to_ne_bytes()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_sort_key_signed {
+    ($($t:ty),*) => {
+        $(
+            impl SortKey for $t {
+                fn to_sort_key(self) -> [u8; 16] {
+                    ((self as i128 as u128) ^ (1 << 127))
+                        .host_to_big_endian()
+                        .to_ne_bytes()
+                }
+            }
+        )*
+    };
+}
+
+impl_sort_key_unsigned!(u8, u16, u32, u64, u128);
+impl_sort_key_signed!(i8, i16, i32, i64, i128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_magnitude() {
+        assert_eq!(read_i32_be_sign_magnitude(&[0x00, 0x00, 0x00, 0x05]), Ok(5));
+        assert_eq!(
+            read_i32_be_sign_magnitude(&[0x80, 0x00, 0x00, 0x05]),
+            Ok(-5)
+        );
+        assert_eq!(
+            read_i32_be_sign_magnitude(&[0xff, 0xff, 0xff, 0xff]),
+            Ok(-i32::MAX)
+        );
+
+        // both zero encodings decode to zero
+        assert_eq!(read_i32_be_sign_magnitude(&[0x00, 0x00, 0x00, 0x00]), Ok(0));
+        assert_eq!(read_i32_be_sign_magnitude(&[0x80, 0x00, 0x00, 0x00]), Ok(0));
+    }
+
+    #[test]
+    fn test_ones_complement() {
+        assert_eq!(
+            read_i32_be_ones_complement(&[0x00, 0x00, 0x00, 0x05]),
+            Ok(5)
+        );
+        assert_eq!(
+            read_i32_be_ones_complement(&[0xff, 0xff, 0xff, 0xfa]),
+            Ok(-5)
+        );
+        assert_eq!(
+            read_i32_be_ones_complement(&[0x80, 0x00, 0x00, 0x00]),
+            Ok(-i32::MAX)
+        );
+
+        // both zero encodings decode to zero
+        assert_eq!(
+            read_i32_be_ones_complement(&[0x00, 0x00, 0x00, 0x00]),
+            Ok(0)
+        );
+        assert_eq!(
+            read_i32_be_ones_complement(&[0xff, 0xff, 0xff, 0xff]),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn test_sign_magnitude_short_input() {
+        let short = Err(EndianError::UnexpectedEof {
+            needed: 4,
+            available: 2,
+        });
+        assert_eq!(read_i32_be_sign_magnitude(&[0x80, 0x00]), short);
+        assert_eq!(read_i32_be_ones_complement(&[0xff, 0xff]), short);
+    }
+
+    #[test]
+    fn test_sort_key_mixed_unsigned() {
+        let keys = [
+            0u64.to_sort_key(),
+            1u16.to_sort_key(),
+            255u16.to_sort_key(),
+            256u64.to_sort_key(),
+            u16::MAX.to_sort_key(),
+            65_536u64.to_sort_key(),
+            u64::MAX.to_sort_key(),
+        ];
+
+        // byte-wise order of the keys matches numeric order
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(7u16.to_sort_key(), 7u64.to_sort_key());
+        assert_eq!(0x0102u16.to_sort_key()[14..], [0x01, 0x02]);
+    }
+
+    #[test]
+    fn test_sort_key_mixed_signed() {
+        let keys = [
+            i64::MIN.to_sort_key(),
+            (-300i16).to_sort_key(),
+            (-1i64).to_sort_key(),
+            0i16.to_sort_key(),
+            1i64.to_sort_key(),
+            i16::MAX.to_sort_key(),
+            i64::MAX.to_sort_key(),
+        ];
+
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!((-5i16).to_sort_key(), (-5i64).to_sort_key());
+    }
+
+    #[test]
+    fn test_group_varint_mixed_widths() {
+        // lengths 1, 4, 2, 3 -> codes 0, 3, 1, 2
+        let group = [
+            0b00_11_01_10,
+            0x7f,
+            0x12,
+            0x34,
+            0x56,
+            0x78,
+            0xab,
+            0xcd,
+            0x01,
+            0x00,
+            0x00,
+            0xff, // trailing byte is not consumed
+        ];
+
+        assert_eq!(
+            read_group_varint_be(&group),
+            Ok(([0x7f, 0x12345678, 0xabcd, 0x010000], 11))
+        );
+    }
+
+    #[test]
+    fn test_group_varint_truncated() {
+        assert_eq!(
+            read_group_varint_be(&[]),
+            Err(EndianError::UnexpectedEof {
+                needed: 1,
+                available: 0
+            })
+        );
+        assert_eq!(
+            read_group_varint_be(&[0xff, 0x00, 0x00]),
+            Err(EndianError::UnexpectedEof {
+                needed: 17,
+                available: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_be_varint_single_byte() {
+        assert_eq!(read_be_varint(&[0x00]), Ok((0, 1)));
+        assert_eq!(read_be_varint(&[0x40, 0xff]), Ok((0x40, 1)));
+        assert_eq!(read_be_varint(&[0x7f]), Ok((0x7f, 1)));
+    }
+
+    #[test]
+    fn test_be_varint_multi_byte() {
+        assert_eq!(read_be_varint(&[0x81, 0x00]), Ok((0x80, 2)));
+        assert_eq!(read_be_varint(&[0xff, 0x7f]), Ok((0x3fff, 2)));
+        assert_eq!(read_be_varint(&[0x81, 0x80, 0x00, 0x01]), Ok((0x4000, 3)));
+        assert_eq!(read_be_varint(&[0x86, 0xc3, 0x17]), Ok((0x1_a197, 3)));
+
+        // u64::MAX takes ten bytes, the first holding a single bit
+        let mut max = [0xffu8; 10];
+        max[0] = 0x81;
+        max[9] = 0x7f;
+        assert_eq!(read_be_varint(&max), Ok((u64::MAX, 10)));
+    }
+
+    #[test]
+    fn test_be_varint_errors() {
+        assert_eq!(
+            read_be_varint(&[0x81, 0x80]),
+            Err(EndianError::UnexpectedEof {
+                needed: 3,
+                available: 2
+            })
+        );
+        assert_eq!(
+            read_be_varint(&[]),
+            Err(EndianError::UnexpectedEof {
+                needed: 1,
+                available: 0
+            })
+        );
+
+        let mut too_big = [0xffu8; 10];
+        too_big[0] = 0x82;
+        too_big[9] = 0x7f;
+        assert_eq!(read_be_varint(&too_big), Err(EndianError::Overflow));
+    }
+
+    #[test]
+    fn test_offset_binary_midscale() {
+        assert_eq!(read_offset_binary_be(&[0x80, 0x00], 2, 0x8000), Ok(0));
+        assert_eq!(read_offset_binary_be(&[0x08, 0x00], 2, 0x800), Ok(0));
+        assert_eq!(read_offset_binary_be(&[0x80], 1, 128), Ok(0));
+
+        let mut buf = [0xffu8; 2];
+        assert_eq!(write_offset_binary_be(0, 2, 0x8000, &mut buf), Ok(2));
+        assert_eq!(buf, [0x80, 0x00]);
+    }
+
+    #[test]
+    fn test_offset_binary_extremes() {
+        assert_eq!(read_offset_binary_be(&[0x00, 0x00], 2, 0x8000), Ok(-32768));
+        assert_eq!(read_offset_binary_be(&[0xff, 0xff], 2, 0x8000), Ok(32767));
+        assert_eq!(read_offset_binary_be(&[0x00; 8], 8, 1 << 63), Ok(i64::MIN));
+        assert_eq!(read_offset_binary_be(&[0xff; 8], 8, 1 << 63), Ok(i64::MAX));
+
+        let mut buf = [0u8; 8];
+        assert_eq!(
+            write_offset_binary_be(i64::MIN, 8, 1 << 63, &mut buf),
+            Ok(8)
+        );
+        assert_eq!(buf, [0x00; 8]);
+        assert_eq!(
+            write_offset_binary_be(i64::MAX, 8, 1 << 63, &mut buf),
+            Ok(8)
+        );
+        assert_eq!(buf, [0xff; 8]);
+        assert_eq!(write_offset_binary_be(32767, 2, 0x8000, &mut buf), Ok(2));
+        assert_eq!(buf[..2], [0xff, 0xff]);
+    }
+
+    #[test]
+    fn test_offset_binary_errors() {
+        let mut buf = [0u8; 2];
+
+        // one past either end of the field range
+        assert_eq!(
+            write_offset_binary_be(32768, 2, 0x8000, &mut buf),
+            Err(EndianError::Overflow)
+        );
+        assert_eq!(
+            write_offset_binary_be(-32769, 2, 0x8000, &mut buf),
+            Err(EndianError::Overflow)
+        );
+        assert_eq!(
+            read_offset_binary_be(&[0xff; 8], 8, 0),
+            Err(EndianError::Overflow)
+        );
+        assert_eq!(
+            read_offset_binary_be(&[0x00; 9], 9, 0),
+            Err(EndianError::InvalidWidth(9))
+        );
+        assert_eq!(
+            read_offset_binary_be(&[0x80], 2, 0x8000),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_encode_minimal_be() {
+        let cases: [(u64, &[u8]); 5] = [
+            (0, &[0x00]),
+            (255, &[0xff]),
+            (256, &[0x01, 0x00]),
+            (0x0102_0304_0506, &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            (u64::MAX, &[0xff; 8]),
+        ];
+
+        for (value, expected) in cases {
+            let (len, bytes) = encode_minimal_be(value);
+            assert_eq!(&bytes[..len], expected);
+            assert!(bytes[len..].iter().all(|&byte| byte == 0));
+            assert_eq!(decode_minimal_be(&bytes[..len]), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_decode_minimal_be() {
+        assert_eq!(decode_minimal_be(&[0x00, 0x00, 0x01]), Ok(1));
+        assert_eq!(decode_minimal_be(&[]), Err(EndianError::InvalidWidth(0)));
+        assert_eq!(
+            decode_minimal_be(&[0x01; 9]),
+            Err(EndianError::InvalidWidth(9))
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! in-place conversions of whole typed slices
+
+use crate::Endianness;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// apply `convert` to every element of `values` in place
+#[inline]
+fn convert_slice<T: Endianness + Copy, F: Fn(T) -> T>(values: &mut [T], convert: F) {
+    for value in values {
+        *value = convert(*value);
+    }
+}
+
+/// convert every element of `values` from big endian to host byte order
+///
+/// # examples
+///
+/// ```
+/// use endbyte::big_endian_to_host_slice;
+///
+/// let mut words = [u16::from_ne_bytes([0x12, 0x34])];
+/// big_endian_to_host_slice(&mut words);
+/// assert_eq!(words, [0x1234]);
+/// ```
+pub fn big_endian_to_host_slice<T: Endianness + Copy>(values: &mut [T]) {
+    convert_slice(values, T::big_endian_to_host);
+}
+
+/// convert every element of `values` from host byte order to big endian
+pub fn host_to_big_endian_slice<T: Endianness + Copy>(values: &mut [T]) {
+    convert_slice(values, T::host_to_big_endian);
+}
+
+/// convert every element of `values` from little endian to host byte order
+pub fn little_endian_to_host_slice<T: Endianness + Copy>(values: &mut [T]) {
+    convert_slice(values, T::little_endian_to_host);
+}
+
+/// convert every element of `values` from host byte order to little endian
+pub fn host_to_little_endian_slice<T: Endianness + Copy>(values: &mut [T]) {
+    convert_slice(values, T::host_to_little_endian);
+}
+
+/// in-place conversions of owned vectors
+///
+/// the methods reuse the slice conversions above and never reallocate.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::EndianVecExt;
+///
+/// let mut words = vec![u16::from_ne_bytes([0x12, 0x34])];
+/// words.convert_big_endian_to_host();
+/// assert_eq!(words, [0x1234]);
+/// ```
+#[cfg(feature = "alloc")]
+pub trait EndianVecExt {
+    /// convert every element from big endian to host byte order
+    fn convert_big_endian_to_host(&mut self);
+
+    /// convert every element from host byte order to big endian
+    fn convert_host_to_big_endian(&mut self);
+
+    /// convert every element from little endian to host byte order
+    fn convert_little_endian_to_host(&mut self);
+
+    /// convert every element from host byte order to little endian
+    fn convert_host_to_little_endian(&mut self);
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Endianness + Copy> EndianVecExt for Vec<T> {
+    fn convert_big_endian_to_host(&mut self) {
+        big_endian_to_host_slice(self);
+    }
+
+    fn convert_host_to_big_endian(&mut self) {
+        host_to_big_endian_slice(self);
+    }
+
+    fn convert_little_endian_to_host(&mut self) {
+        little_endian_to_host_slice(self);
+    }
+
+    fn convert_host_to_little_endian(&mut self) {
+        host_to_little_endian_slice(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice_u16() {
+        let host = [0x1234u16, 0x0001, 0xff00];
+
+        let mut values = host;
+        host_to_big_endian_slice(&mut values);
+        for (converted, value) in values.iter().zip(host) {
+            assert_eq!(*converted, value.host_to_big_endian());
+        }
+        big_endian_to_host_slice(&mut values);
+        assert_eq!(values, host);
+
+        host_to_little_endian_slice(&mut values);
+        assert_eq!(values[0].to_ne_bytes(), [0x34, 0x12]);
+        little_endian_to_host_slice(&mut values);
+        assert_eq!(values, host);
+    }
+
+    #[test]
+    fn test_slice_single_byte_untouched() {
+        let mut bytes = [0x12u8, 0x34, 0x56];
+        host_to_big_endian_slice(&mut bytes);
+        little_endian_to_host_slice(&mut bytes);
+        assert_eq!(bytes, [0x12, 0x34, 0x56]);
+
+        let mut signed = [-1i8, 2];
+        big_endian_to_host_slice(&mut signed);
+        assert_eq!(signed, [-1, 2]);
+
+        // an empty slice is fine too
+        host_to_little_endian_slice::<u64>(&mut []);
+    }
+
+    #[test]
+    fn test_slice_matches_scalar() {
+        // checked against core's conversions rather than the Endianness impls
+        // the slice functions are built from
+        let host: [u32; 1000] = core::array::from_fn(|i| (i as u32).wrapping_mul(0x9e37_79b9));
+
+        let mut values = host;
+        host_to_big_endian_slice(&mut values);
+        for (converted, value) in values.iter().zip(host) {
+            assert_eq!(*converted, value.to_be());
+        }
+
+        host_to_little_endian_slice(&mut values[..997]);
+        for (converted, value) in values[..997].iter().zip(host) {
+            assert_eq!(*converted, value.to_be().to_le());
+        }
+        little_endian_to_host_slice(&mut values[..997]);
+        big_endian_to_host_slice(&mut values);
+        assert_eq!(values, host);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_vec_ext() {
+        let host = alloc::vec![0x12345678u32, 1, u32::MAX - 1];
+        let mut values = host.clone();
+        let data = values.as_ptr();
+
+        values.convert_host_to_big_endian();
+        assert_eq!(values[0].to_ne_bytes(), [0x12, 0x34, 0x56, 0x78]);
+        values.convert_big_endian_to_host();
+        assert_eq!(values, host);
+
+        values.convert_host_to_little_endian();
+        assert_eq!(values[0].to_ne_bytes(), [0x78, 0x56, 0x34, 0x12]);
+        values.convert_little_endian_to_host();
+        assert_eq!(values, host);
+
+        // converted in place without reallocating
+        assert_eq!(values.as_ptr(), data);
+    }
+}
@@ -0,0 +1,188 @@
+//! byte swaps written as plain shifts and masks
+//!
+//! these produce the same results as the `swap_bytes` intrinsics and serve as
+//! a readable reference implementation. note that the optimizer recognises the
+//! pattern and may still compile them to a single byte swap instruction.
+//!
+//! enabling the `no-intrinsics` feature routes every
+//! [`Endianness`](crate::Endianness) conversion through copies of these swaps
+//! that pass the operands of every step through [`core::hint::black_box`], so
+//! the emitted code is the shifts, masks and ors as written. ci builds the
+//! `asm_test` example under the feature and checks it for byte swaps.
+
+// the shifts are spelled out on purpose instead of using rotate intrinsics
+#![allow(clippy::manual_rotate)]
+
+/// reverse the bytes of a `u16` using shifts only
+#[inline]
+pub const fn swap_u16_branchless(value: u16) -> u16 {
+    (value << 8) | (value >> 8)
+}
+
+/// reverse the bytes of a `u32` using shifts and masks only
+#[inline]
+pub const fn swap_u32_branchless(value: u32) -> u32 {
+    let value = ((value & 0x00ff_00ff) << 8) | ((value >> 8) & 0x00ff_00ff);
+    (value << 16) | (value >> 16)
+}
+
+/// reverse the bytes of a `u64` using shifts and masks only
+#[inline]
+pub const fn swap_u64_branchless(value: u64) -> u64 {
+    let value = ((value & 0x00ff_00ff_00ff_00ff) << 8) | ((value >> 8) & 0x00ff_00ff_00ff_00ff);
+    let value = ((value & 0x0000_ffff_0000_ffff) << 16) | ((value >> 16) & 0x0000_ffff_0000_ffff);
+    (value << 32) | (value >> 32)
+}
+
+/// reverse the bytes of a `u128` using shifts and masks only
+#[inline]
+pub const fn swap_u128_branchless(value: u128) -> u128 {
+    const BYTES: u128 = 0x00ff_00ff_00ff_00ff_00ff_00ff_00ff_00ff;
+    const HALVES: u128 = 0x0000_ffff_0000_ffff_0000_ffff_0000_ffff;
+    const WORDS: u128 = 0x0000_0000_ffff_ffff_0000_0000_ffff_ffff;
+
+    let value = ((value & BYTES) << 8) | ((value >> 8) & BYTES);
+    let value = ((value & HALVES) << 16) | ((value >> 16) & HALVES);
+    let value = ((value & WORDS) << 32) | ((value >> 32) & WORDS);
+    (value << 64) | (value >> 64)
+}
+
+// the branchless swaps with every step hidden from the optimizer, so that it
+// cannot fold them back into a byte swap instruction
+#[cfg(feature = "no-intrinsics")]
+mod opaque {
+    use core::hint::black_box;
+
+    // one exchange step: swap the `shift` bit groups selected by `mask` with
+    // their neighbours. both operands go through black_box so the or cannot be
+    // matched as part of a byte swap.
+    macro_rules! step {
+        ($value:expr, $mask:expr, $shift:expr) => {
+            (black_box($value & $mask) << $shift) | (black_box($value >> $shift) & $mask)
+        };
+        ($value:expr, $shift:expr) => {
+            black_box($value << $shift) | black_box($value >> $shift)
+        };
+    }
+
+    #[inline]
+    pub(super) fn swap_u16(value: u16) -> u16 {
+        step!(value, 8)
+    }
+
+    #[inline]
+    pub(super) fn swap_u32(value: u32) -> u32 {
+        let value = step!(value, 0x00ff_00ff, 8);
+        step!(value, 16)
+    }
+
+    #[inline]
+    pub(super) fn swap_u64(value: u64) -> u64 {
+        let value = step!(value, 0x00ff_00ff_00ff_00ff, 8);
+        let value = step!(value, 0x0000_ffff_0000_ffff, 16);
+        step!(value, 32)
+    }
+
+    #[inline]
+    pub(super) fn swap_u128(value: u128) -> u128 {
+        let value = step!(value, 0x00ff_00ff_00ff_00ff_00ff_00ff_00ff_00ff, 8);
+        let value = step!(value, 0x0000_ffff_0000_ffff_0000_ffff_0000_ffff, 16);
+        let value = step!(value, 0x0000_0000_ffff_ffff_0000_0000_ffff_ffff, 32);
+        step!(value, 64)
+    }
+}
+
+// byte swap used by the Endianness impls, selected by the no-intrinsics feature
+pub(crate) trait Swap {
+    fn swap(self) -> Self;
+}
+
+macro_rules! impl_swap {
+    ($($t:ty => $opaque:ident),*) => {
+        $(
+            impl Swap for $t {
+                #[inline]
+                fn swap(self) -> Self {
+                    #[cfg(not(feature = "no-intrinsics"))]
+                    {
+                        self.swap_bytes()
+                    }
+                    #[cfg(feature = "no-intrinsics")]
+                    {
+                        opaque::$opaque(self)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_swap!(
+    u16 => swap_u16,
+    u32 => swap_u32,
+    u64 => swap_u64,
+    u128 => swap_u128
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // simple xorshift generator for reproducible test values
+    fn values() -> impl Iterator<Item = u128> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        core::iter::from_fn(move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let high = state;
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            Some((u128::from(high) << 64) | u128::from(state))
+        })
+        .take(10_000)
+    }
+
+    #[test]
+    fn test_branchless_u16_full_range() {
+        for value in 0..=u16::MAX {
+            assert_eq!(swap_u16_branchless(value), value.swap_bytes());
+        }
+    }
+
+    #[test]
+    fn test_branchless_matches_intrinsics() {
+        for value in values() {
+            assert_eq!(
+                swap_u32_branchless(value as u32),
+                (value as u32).swap_bytes()
+            );
+            assert_eq!(
+                swap_u64_branchless(value as u64),
+                (value as u64).swap_bytes()
+            );
+            assert_eq!(swap_u128_branchless(value), value.swap_bytes());
+        }
+    }
+
+    #[test]
+    fn test_branchless_known_values() {
+        assert_eq!(swap_u32_branchless(0x12345678), 0x78563412);
+        assert_eq!(swap_u64_branchless(0x123456789abcdef0), 0xf0debc9a78563412);
+        assert_eq!(
+            swap_u128_branchless(0x000102030405060708090a0b0c0d0e0f),
+            0x0f0e0d0c0b0a09080706050403020100
+        );
+    }
+
+    #[test]
+    fn test_selected_swap_matches_intrinsics() {
+        for value in values() {
+            assert_eq!(Swap::swap(value as u16), (value as u16).swap_bytes());
+            assert_eq!(Swap::swap(value as u32), (value as u32).swap_bytes());
+            assert_eq!(Swap::swap(value as u64), (value as u64).swap_bytes());
+            assert_eq!(Swap::swap(value), value.swap_bytes());
+        }
+    }
+}
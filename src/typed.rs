@@ -0,0 +1,137 @@
+//! distinct host, big endian and little endian integer newtypes
+//!
+//! a big endian value can only become a host value through an explicit
+//! conversion, so mixing the two up is a type error:
+//!
+//! ```compile_fail
+//! use endbyte::{BeU32, HostU32};
+//!
+//! fn process(_value: HostU32) {}
+//!
+//! process(BeU32::from_raw(0x12345678));
+//! ```
+
+use crate::Endianness;
+
+macro_rules! impl_typed_order {
+    ($($t:ty => $host:ident, $be:ident, $le:ident;)*) => {
+        $(
+            #[doc = concat!("a `", stringify!($t), "` in host byte order")]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+            #[repr(transparent)]
+            pub struct $host($t);
+
+            #[doc = concat!("a `", stringify!($t), "` stored in big endian byte order")]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+            #[repr(transparent)]
+            pub struct $be($t);
+
+            #[doc = concat!("a `", stringify!($t), "` stored in little endian byte order")]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+            #[repr(transparent)]
+            pub struct $le($t);
+
+            impl $host {
+                /// wrap a value in host byte order
+                pub const fn new(value: $t) -> Self {
+                    Self(value)
+                }
+
+                /// return the wrapped host order value
+                pub const fn get(self) -> $t {
+                    self.0
+                }
+
+                /// convert to big endian byte order
+                pub fn to_be(self) -> $be {
+                    $be(self.0.host_to_big_endian())
+                }
+
+                /// convert to little endian byte order
+                pub fn to_le(self) -> $le {
+                    $le(self.0.host_to_little_endian())
+                }
+            }
+
+            impl $be {
+                /// wrap a raw value that is already in big endian byte order
+                pub const fn from_raw(raw: $t) -> Self {
+                    Self(raw)
+                }
+
+                /// return the raw big endian value
+                pub const fn raw(self) -> $t {
+                    self.0
+                }
+
+                /// convert to host byte order
+                pub fn to_host(self) -> $host {
+                    $host(self.0.big_endian_to_host())
+                }
+            }
+
+            impl $le {
+                /// wrap a raw value that is already in little endian byte order
+                pub const fn from_raw(raw: $t) -> Self {
+                    Self(raw)
+                }
+
+                /// return the raw little endian value
+                pub const fn raw(self) -> $t {
+                    self.0
+                }
+
+                /// convert to host byte order
+                pub fn to_host(self) -> $host {
+                    $host(self.0.little_endian_to_host())
+                }
+            }
+
+            impl From<$t> for $host {
+                fn from(value: $t) -> Self {
+                    Self(value)
+                }
+            }
+
+            impl From<$host> for $t {
+                fn from(value: $host) -> Self {
+                    value.0
+                }
+            }
+        )*
+    };
+}
+
+impl_typed_order! {
+    u16 => HostU16, BeU16, LeU16;
+    u32 => HostU32, BeU32, LeU32;
+    u64 => HostU64, BeU64, LeU64;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_round_trip() {
+        let host = HostU32::new(0x12345678);
+
+        assert_eq!(host.to_be().to_host(), host);
+        assert_eq!(host.to_le().to_host(), host);
+        assert_eq!(HostU16::new(0x1234).to_be().to_host().get(), 0x1234);
+        assert_eq!(HostU64::from(u64::MAX).to_le().to_host().get(), u64::MAX);
+    }
+
+    #[test]
+    fn test_typed_raw_layout() {
+        let be = HostU32::new(0x12345678).to_be();
+        assert_eq!(be.raw().to_ne_bytes(), [0x12, 0x34, 0x56, 0x78]);
+
+        let le = HostU32::new(0x12345678).to_le();
+        assert_eq!(le.raw().to_ne_bytes(), [0x78, 0x56, 0x34, 0x12]);
+
+        let raw = u16::from_ne_bytes([0x12, 0x34]);
+        assert_eq!(BeU16::from_raw(raw).to_host().get(), 0x1234);
+        assert_eq!(LeU16::from_raw(raw).to_host().get(), 0x3412);
+    }
+}
@@ -0,0 +1,168 @@
+//! free functions writing integers to the front of a byte slice
+
+use crate::{EndianError, EndianSerialize, Endianness, EndiannessType};
+
+// copy `bytes` to the front of `buf`, returning the number of bytes written
+#[inline]
+pub(crate) fn put<const N: usize>(buf: &mut [u8], bytes: [u8; N]) -> Result<usize, EndianError> {
+    let available = buf.len();
+    let head = buf.get_mut(..N).ok_or(EndianError::UnexpectedEof {
+        needed: N,
+        available,
+    })?;
+    head.copy_from_slice(&bytes);
+    Ok(N)
+}
+
+/// write the low `width` bytes of `value` in big endian to the front of `buf`
+///
+/// formats often declare a field narrower than the integer type holding it.
+/// `width` must be between 1 and 4. returns the number of bytes written.
+///
+/// # errors
+///
+/// returns [`EndianError::InvalidWidth`] for an unsupported width,
+/// [`EndianError::Overflow`] if `value` does not fit in `width` bytes and
+/// [`EndianError::UnexpectedEof`] if `buf` is too short.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::write_u32_be_truncated;
+///
+/// let mut buf = [0u8; 3];
+/// assert_eq!(write_u32_be_truncated(0x123456, 3, &mut buf), Ok(3));
+/// assert_eq!(buf, [0x12, 0x34, 0x56]);
+/// ```
+pub fn write_u32_be_truncated(
+    value: u32,
+    width: usize,
+    buf: &mut [u8],
+) -> Result<usize, EndianError> {
+    if !(1..=4).contains(&width) {
+        return Err(EndianError::InvalidWidth(width));
+    }
+
+    if width < 4 && value >> (8 * width) != 0 {
+        return Err(EndianError::Overflow);
+    }
+
+    let available = buf.len();
+    let field = buf.get_mut(..width).ok_or(EndianError::UnexpectedEof {
+        needed: width,
+        available,
+    })?;
+
+    let bytes = value.host_to_big_endian().to_ne_bytes();
+    field.copy_from_slice(&bytes[4 - width..]);
+    Ok(width)
+}
+
+/// write `data` prefixed by its length as a big endian `u16`
+///
+/// returns the number of bytes written, `2 + data.len()`.
+///
+/// # errors
+///
+/// returns [`EndianError::Overflow`] if `data` is longer than `u16::MAX` bytes
+/// and [`EndianError::UnexpectedEof`] if `buf` is too short. nothing is written
+/// on error.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::write_prefixed_u16_be;
+///
+/// let mut buf = [0u8; 8];
+/// assert_eq!(write_prefixed_u16_be(&mut buf, b"abc"), Ok(5));
+/// assert_eq!(buf[..5], [0x00, 0x03, b'a', b'b', b'c']);
+/// ```
+pub fn write_prefixed_u16_be(buf: &mut [u8], data: &[u8]) -> Result<usize, EndianError> {
+    let len = u16::try_from(data.len()).map_err(|_| EndianError::Overflow)?;
+
+    let needed = 2 + data.len();
+    if buf.len() < needed {
+        return Err(EndianError::UnexpectedEof {
+            needed,
+            available: buf.len(),
+        });
+    }
+
+    len.write_bytes(buf, EndiannessType::BigEndian)?;
+    buf[2..needed].copy_from_slice(data);
+    Ok(needed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_u32_be_truncated() {
+        let mut buf = [0xffu8; 4];
+
+        assert_eq!(write_u32_be_truncated(0xabcdef, 3, &mut buf), Ok(3));
+        assert_eq!(buf, [0xab, 0xcd, 0xef, 0xff]);
+
+        assert_eq!(write_u32_be_truncated(0x42, 1, &mut buf), Ok(1));
+        assert_eq!(buf[0], 0x42);
+
+        assert_eq!(write_u32_be_truncated(u32::MAX, 4, &mut buf), Ok(4));
+        assert_eq!(buf, [0xff; 4]);
+    }
+
+    #[test]
+    fn test_write_u32_be_truncated_errors() {
+        let mut buf = [0u8; 4];
+
+        assert_eq!(
+            write_u32_be_truncated(0x01000000, 3, &mut buf),
+            Err(EndianError::Overflow)
+        );
+        assert_eq!(
+            write_u32_be_truncated(1, 5, &mut buf),
+            Err(EndianError::InvalidWidth(5))
+        );
+        assert_eq!(
+            write_u32_be_truncated(1, 0, &mut buf),
+            Err(EndianError::InvalidWidth(0))
+        );
+        assert_eq!(
+            write_u32_be_truncated(1, 3, &mut buf[..2]),
+            Err(EndianError::UnexpectedEof {
+                needed: 3,
+                available: 2
+            })
+        );
+        assert_eq!(buf, [0; 4]);
+    }
+
+    #[test]
+    fn test_write_prefixed_u16_be() {
+        let mut buf = [0xffu8; 8];
+
+        assert_eq!(write_prefixed_u16_be(&mut buf, &[0xaa, 0xbb]), Ok(4));
+        assert_eq!(buf[..4], [0x00, 0x02, 0xaa, 0xbb]);
+
+        assert_eq!(write_prefixed_u16_be(&mut buf, &[]), Ok(2));
+        assert_eq!(buf[..2], [0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_write_prefixed_u16_be_errors() {
+        let mut buf = [0u8; 4];
+
+        assert_eq!(
+            write_prefixed_u16_be(&mut buf, &[0; 65_536]),
+            Err(EndianError::Overflow)
+        );
+        assert_eq!(
+            write_prefixed_u16_be(&mut buf, &[1, 2, 3]),
+            Err(EndianError::UnexpectedEof {
+                needed: 5,
+                available: 4
+            })
+        );
+        assert_eq!(buf, [0; 4]);
+    }
+}
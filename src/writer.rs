@@ -0,0 +1,647 @@
+//! cursor for writing endian values to a byte slice
+
+use crate::{EndianError, EndianSerialize, EndiannessType};
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+// generate the big and little endian write methods for each type
+macro_rules! impl_write_methods {
+    ($($t:ty, $bytes:literal => $be:ident, $le:ident;)*) => {
+        $(
+            #[doc = concat!("write a big endian `", stringify!($t), "`")]
+            ///
+            /// # errors
+            ///
+            #[doc = concat!(
+                "returns [`EndianError::UnexpectedEof`] if fewer than ",
+                stringify!($bytes),
+                " bytes remain."
+            )]
+            pub fn $be(&mut self, value: $t) -> Result<usize, EndianError> {
+                self.write(value, EndiannessType::BigEndian)
+            }
+
+            #[doc = concat!("write a little endian `", stringify!($t), "`")]
+            ///
+            /// # errors
+            ///
+            #[doc = concat!(
+                "returns [`EndianError::UnexpectedEof`] if fewer than ",
+                stringify!($bytes),
+                " bytes remain."
+            )]
+            pub fn $le(&mut self, value: $t) -> Result<usize, EndianError> {
+                self.write(value, EndiannessType::LittleEndian)
+            }
+        )*
+    };
+}
+
+/// cursor writing endian values to the front of a mutable byte slice
+///
+/// every successful write advances the position past the written bytes. a
+/// failed write leaves both the position and the buffer unchanged.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::EndianWriter;
+///
+/// let mut buf = [0u8; 6];
+/// let mut writer = EndianWriter::new(&mut buf);
+/// writer.write_u16_be(0x1234).unwrap();
+/// writer.write_u32_be(0xdeadbeef).unwrap();
+/// assert_eq!(writer.remaining(), 0);
+/// assert_eq!(buf, [0x12, 0x34, 0xde, 0xad, 0xbe, 0xef]);
+/// ```
+#[derive(Debug)]
+pub struct EndianWriter<'a> {
+    buf: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> EndianWriter<'a> {
+    /// create a writer positioned at the start of `buf`
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, position: 0 }
+    }
+
+    /// current offset from the start of the buffer
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// number of bytes left to write
+    pub const fn remaining(&self) -> usize {
+        self.buf.len() - self.position
+    }
+
+    /// write `value` in `order`, returning the number of bytes written
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if too few bytes remain.
+    pub fn write<T: EndianSerialize>(
+        &mut self,
+        value: T,
+        order: EndiannessType,
+    ) -> Result<usize, EndianError> {
+        let written = value.write_bytes(&mut self.buf[self.position..], order)?;
+        self.position += written;
+        Ok(written)
+    }
+
+    /// copy `bytes` to the buffer unchanged, returning the number of bytes written
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if fewer than `bytes.len()` bytes
+    /// remain.
+    pub fn write_slice(&mut self, bytes: &[u8]) -> Result<usize, EndianError> {
+        let available = self.remaining();
+        let end = self.position + bytes.len();
+        self.buf
+            .get_mut(self.position..end)
+            .ok_or(EndianError::UnexpectedEof {
+                needed: bytes.len(),
+                available,
+            })?
+            .copy_from_slice(bytes);
+        self.position = end;
+        Ok(bytes.len())
+    }
+
+    /// write a `u8`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if no bytes remain.
+    pub fn write_u8(&mut self, value: u8) -> Result<usize, EndianError> {
+        self.write(value, EndiannessType::BigEndian)
+    }
+
+    /// write an `i8`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if no bytes remain.
+    pub fn write_i8(&mut self, value: i8) -> Result<usize, EndianError> {
+        self.write(value, EndiannessType::BigEndian)
+    }
+
+    impl_write_methods! {
+        u16, 2 => write_u16_be, write_u16_le;
+        u32, 4 => write_u32_be, write_u32_le;
+        u64, 8 => write_u64_be, write_u64_le;
+        u128, 16 => write_u128_be, write_u128_le;
+        i16, 2 => write_i16_be, write_i16_le;
+        i32, 4 => write_i32_be, write_i32_le;
+        i64, 8 => write_i64_be, write_i64_le;
+        i128, 16 => write_i128_be, write_i128_le;
+    }
+}
+
+// generate the chained big and little endian builder methods for each type
+macro_rules! impl_builder_methods {
+    ($($t:ty => $be:ident, $le:ident, $write_be:ident, $write_le:ident;)*) => {
+        $(
+            #[doc = concat!("append a big endian `", stringify!($t), "`")]
+            ///
+            /// # errors
+            ///
+            /// returns [`EndianError::UnexpectedEof`] if the buffer is full.
+            pub fn $be(&mut self, value: $t) -> Result<&mut Self, EndianError> {
+                self.writer.$write_be(value)?;
+                Ok(self)
+            }
+
+            #[doc = concat!("append a little endian `", stringify!($t), "`")]
+            ///
+            /// # errors
+            ///
+            /// returns [`EndianError::UnexpectedEof`] if the buffer is full.
+            pub fn $le(&mut self, value: $t) -> Result<&mut Self, EndianError> {
+                self.writer.$write_le(value)?;
+                Ok(self)
+            }
+        )*
+    };
+}
+
+/// fluent builder assembling a frame field by field
+///
+/// every method appends one field and returns the builder, so a frame can be
+/// written as a single chain of `?` calls ending in
+/// [`finish`](FrameBuilder::finish).
+///
+/// # examples
+///
+/// ```
+/// use endbyte::FrameBuilder;
+///
+/// # fn main() -> Result<(), endbyte::EndianError> {
+/// let mut buf = [0u8; 8];
+/// let len = FrameBuilder::new(&mut buf).u16_be(0xcafe)?.u8(3)?.bytes(b"abc")?.finish();
+/// assert_eq!(buf[..len], [0xca, 0xfe, 3, b'a', b'b', b'c']);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FrameBuilder<'a> {
+    writer: EndianWriter<'a>,
+}
+
+impl<'a> FrameBuilder<'a> {
+    /// start a frame at the beginning of `buf`
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            writer: EndianWriter::new(buf),
+        }
+    }
+
+    /// append a `u8`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if the buffer is full.
+    pub fn u8(&mut self, value: u8) -> Result<&mut Self, EndianError> {
+        self.writer.write_u8(value)?;
+        Ok(self)
+    }
+
+    /// append an `i8`
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if the buffer is full.
+    pub fn i8(&mut self, value: i8) -> Result<&mut Self, EndianError> {
+        self.writer.write_i8(value)?;
+        Ok(self)
+    }
+
+    impl_builder_methods! {
+        u16 => u16_be, u16_le, write_u16_be, write_u16_le;
+        u32 => u32_be, u32_le, write_u32_be, write_u32_le;
+        u64 => u64_be, u64_le, write_u64_be, write_u64_le;
+        u128 => u128_be, u128_le, write_u128_be, write_u128_le;
+        i16 => i16_be, i16_le, write_i16_be, write_i16_le;
+        i32 => i32_be, i32_le, write_i32_be, write_i32_le;
+        i64 => i64_be, i64_le, write_i64_be, write_i64_le;
+        i128 => i128_be, i128_le, write_i128_be, write_i128_le;
+    }
+
+    /// append raw bytes unchanged
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if `data` does not fit.
+    pub fn bytes(&mut self, data: &[u8]) -> Result<&mut Self, EndianError> {
+        self.writer.write_slice(data)?;
+        Ok(self)
+    }
+
+    /// return the total length of the frame written so far
+    pub fn finish(&self) -> usize {
+        self.writer.position()
+    }
+}
+
+/// writer for type-length-value entries padded to 4 bytes
+///
+/// produces the layout read by [`TlvReader`](crate::TlvReader): a `u16` type
+/// and a `u16` value length in the order chosen at construction, then the
+/// value unchanged, then zero padding up to the next multiple of 4.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{EndiannessType, TlvReader, TlvWriter};
+///
+/// let mut buf = [0u8; 16];
+/// let mut writer = TlvWriter::new(&mut buf, EndiannessType::LittleEndian);
+/// writer.write_entry(7, b"hi").unwrap();
+/// let len = writer.finish();
+///
+/// assert_eq!(buf[..len], [0x07, 0x00, 0x02, 0x00, b'h', b'i', 0x00, 0x00]);
+/// let mut entries = TlvReader::with_order(&buf[..len], EndiannessType::LittleEndian);
+/// assert_eq!(entries.next(), Some(Ok((7, &b"hi"[..]))));
+/// ```
+#[derive(Debug)]
+pub struct TlvWriter<'a> {
+    writer: EndianWriter<'a>,
+    order: EndiannessType,
+}
+
+impl<'a> TlvWriter<'a> {
+    /// start writing entries with headers in `order` at the beginning of `buf`
+    pub fn new(buf: &'a mut [u8], order: EndiannessType) -> Self {
+        Self {
+            writer: EndianWriter::new(buf),
+            order,
+        }
+    }
+
+    /// append an entry of type `kind` holding `value`
+    ///
+    /// returns the number of bytes written, including the padding.
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::Overflow`] if `value` is longer than `u16::MAX`
+    /// bytes and [`EndianError::UnexpectedEof`] if the padded entry does not
+    /// fit. nothing is written on error.
+    pub fn write_entry(&mut self, kind: u16, value: &[u8]) -> Result<usize, EndianError> {
+        let len = u16::try_from(value.len()).map_err(|_| EndianError::Overflow)?;
+
+        let needed = 4 + value.len().next_multiple_of(4);
+        if self.writer.remaining() < needed {
+            return Err(EndianError::UnexpectedEof {
+                needed,
+                available: self.writer.remaining(),
+            });
+        }
+
+        self.writer.write(kind, self.order)?;
+        self.writer.write(len, self.order)?;
+        self.writer.write_slice(value)?;
+        self.writer
+            .write_slice(&[0; 3][..needed - 4 - value.len()])?;
+        Ok(needed)
+    }
+
+    /// return the total length of the entries written so far
+    pub fn finish(&self) -> usize {
+        self.writer.position()
+    }
+}
+
+/// [`EndianWriter`] wrapper that logs every field it writes
+///
+/// each successful write is recorded as `(name, offset, bits, order)`, which
+/// makes it easy to compare a serialized buffer against what the code meant to
+/// write. `bits` is the bit pattern of the value zero-extended to `u64`, so
+/// signed fields are logged as their two's complement bits. failed writes are
+/// not logged.
+///
+/// # examples
+///
+/// ```
+/// use endbyte::{EndianWriter, EndiannessType, TracingWriter};
+///
+/// let mut buf = [0u8; 4];
+/// let mut writer = TracingWriter::new(EndianWriter::new(&mut buf));
+/// writer.write_field("magic", 0xcafeu16, EndiannessType::BigEndian).unwrap();
+/// assert_eq!(
+///     writer.log()[0],
+///     ("magic".into(), 0, 0xcafe, EndiannessType::BigEndian)
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct TracingWriter<'a> {
+    writer: EndianWriter<'a>,
+    log: Vec<(String, usize, u64, EndiannessType)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> TracingWriter<'a> {
+    /// wrap `writer` with an empty log
+    pub fn new(writer: EndianWriter<'a>) -> Self {
+        Self {
+            writer,
+            log: Vec::new(),
+        }
+    }
+
+    /// write `value` in `order` and record it under `name`
+    ///
+    /// returns the number of bytes written.
+    ///
+    /// # errors
+    ///
+    /// returns [`EndianError::UnexpectedEof`] if too few bytes remain and
+    /// [`EndianError::InvalidWidth`] if `T` is wider than 8 bytes, since its
+    /// bits would not fit the log.
+    pub fn write_field<T: EndianSerialize + Copy>(
+        &mut self,
+        name: &str,
+        value: T,
+        order: EndiannessType,
+    ) -> Result<usize, EndianError> {
+        // the value's bytes in big endian, right aligned, read back as a u64
+        let mut scratch = [0u8; 8];
+        let start = scratch
+            .len()
+            .checked_sub(T::BYTES)
+            .ok_or(EndianError::InvalidWidth(T::BYTES))?;
+        value.write_bytes(&mut scratch[start..], EndiannessType::BigEndian)?;
+        let bits = u64::from_bytes(&scratch, EndiannessType::BigEndian)?;
+
+        let offset = self.writer.position();
+        let written = self.writer.write(value, order)?;
+        self.log.push((name.into(), offset, bits, order));
+        Ok(written)
+    }
+
+    /// fields written so far as `(name, offset, bits, order)`
+    pub fn log(&self) -> &[(String, usize, u64, EndiannessType)] {
+        &self.log
+    }
+
+    /// consume the wrapper and return the write log
+    pub fn into_log(self) -> Vec<(String, usize, u64, EndiannessType)> {
+        self.log
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writer_advances() {
+        let mut buf = [0u8; 6];
+        let mut writer = EndianWriter::new(&mut buf);
+
+        assert_eq!(writer.write_u32_be(0x01020304), Ok(4));
+        assert_eq!(writer.position(), 4);
+
+        // a failed write does not move the cursor
+        assert_eq!(
+            writer.write_u32_be(0),
+            Err(EndianError::UnexpectedEof {
+                needed: 4,
+                available: 2
+            })
+        );
+        assert_eq!(writer.position(), 4);
+        assert_eq!(writer.write(0x0605u16, EndiannessType::LittleEndian), Ok(2));
+        assert_eq!(buf, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_write_then_read_back() {
+        let mut buf = [0u8; 32];
+        let mut writer = EndianWriter::new(&mut buf);
+
+        writer.write_u8(0x7f).unwrap();
+        writer.write_i8(-2).unwrap();
+        writer.write_u16_le(0x1234).unwrap();
+        writer.write_i32_be(-100_000).unwrap();
+        writer.write_u64_le(u64::MAX - 1).unwrap();
+        writer.write_i64_be(i64::MIN).unwrap();
+        writer.write_u32_be(0xdeadbeef).unwrap();
+        let written = writer.position();
+        assert_eq!(written, 28);
+        assert_eq!(writer.remaining(), 4);
+
+        let mut reader = crate::EndianReader::new(&buf[..written]);
+        assert_eq!(reader.read_u8(), Ok(0x7f));
+        assert_eq!(reader.read_i8(), Ok(-2));
+        assert_eq!(reader.read_u16_le(), Ok(0x1234));
+        assert_eq!(reader.read_i32_be(), Ok(-100_000));
+        assert_eq!(reader.read_u64_le(), Ok(u64::MAX - 1));
+        assert_eq!(reader.read_i64_be(), Ok(i64::MIN));
+        assert_eq!(reader.read_u32_be(), Ok(0xdeadbeef));
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn test_writer_overflow() {
+        let mut buf = [0u8; 20];
+        let mut writer = EndianWriter::new(&mut buf);
+
+        assert_eq!(writer.write_u128_le(1), Ok(16));
+        assert_eq!(
+            writer.write_i64_le(-1),
+            Err(EndianError::UnexpectedEof {
+                needed: 8,
+                available: 4
+            })
+        );
+        assert_eq!(writer.write_i16_be(-1), Ok(2));
+        assert!(writer.write_i128_be(0).is_err());
+        assert_eq!(writer.position(), 18);
+        assert_eq!(buf[16..], [0xff, 0xff, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_write_slice() {
+        let mut buf = [0u8; 4];
+        let mut writer = EndianWriter::new(&mut buf);
+
+        assert_eq!(writer.write_slice(&[1, 2, 3]), Ok(3));
+        assert_eq!(
+            writer.write_slice(&[4, 5]),
+            Err(EndianError::UnexpectedEof {
+                needed: 2,
+                available: 1
+            })
+        );
+        assert_eq!(writer.write_slice(&[]), Ok(0));
+        assert_eq!(writer.position(), 3);
+        assert_eq!(buf, [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_tlv_round_trip() {
+        use crate::TlvReader;
+
+        for order in [EndiannessType::BigEndian, EndiannessType::LittleEndian] {
+            let mut buf = [0xeeu8; 32];
+            let mut writer = TlvWriter::new(&mut buf, order);
+            assert_eq!(writer.write_entry(0x0102, &[0xaa, 0xbb, 0xcc]), Ok(8));
+            assert_eq!(writer.write_entry(0x0304, &[]), Ok(4));
+            assert_eq!(writer.write_entry(0x0506, &[1, 2, 3, 4]), Ok(8));
+            let len = writer.finish();
+            assert_eq!(len, 20);
+
+            let mut entries = TlvReader::with_order(&buf[..len], order);
+            assert_eq!(entries.next(), Some(Ok((0x0102, &[0xaa, 0xbb, 0xcc][..]))));
+            assert_eq!(entries.next(), Some(Ok((0x0304, &[][..]))));
+            assert_eq!(entries.next(), Some(Ok((0x0506, &[1, 2, 3, 4][..]))));
+            assert_eq!(entries.next(), None);
+        }
+    }
+
+    #[test]
+    fn test_tlv_writer_layout() {
+        let mut big = [0u8; 8];
+        let mut little = [0u8; 8];
+        TlvWriter::new(&mut big, EndiannessType::BigEndian)
+            .write_entry(0x0102, &[0x0a, 0x0b])
+            .unwrap();
+        TlvWriter::new(&mut little, EndiannessType::LittleEndian)
+            .write_entry(0x0102, &[0x0a, 0x0b])
+            .unwrap();
+
+        // the header follows the order, the value and padding do not
+        assert_eq!(big, [0x01, 0x02, 0x00, 0x02, 0x0a, 0x0b, 0x00, 0x00]);
+        assert_eq!(little, [0x02, 0x01, 0x02, 0x00, 0x0a, 0x0b, 0x00, 0x00]);
+
+        let mut buf = [0u8; 7];
+        let mut writer = TlvWriter::new(&mut buf, EndiannessType::BigEndian);
+        assert_eq!(
+            writer.write_entry(1, &[0x0a]),
+            Err(EndianError::UnexpectedEof {
+                needed: 8,
+                available: 7
+            })
+        );
+        assert_eq!(writer.finish(), 0);
+    }
+
+    #[test]
+    fn test_frame_builder_mixed_order() -> Result<(), EndianError> {
+        let mut buf = [0u8; 16];
+        let len = FrameBuilder::new(&mut buf)
+            .u32_be(0x01020304)?
+            .u16_le(0x0506)?
+            .i8(-1)?
+            .bytes(&[0xaa, 0xbb])?
+            .i32_le(-2)?
+            .finish();
+
+        assert_eq!(len, 13);
+        assert_eq!(
+            buf[..len],
+            [
+                0x01, 0x02, 0x03, 0x04, // u32 be
+                0x06, 0x05, // u16 le
+                0xff, // i8
+                0xaa, 0xbb, // raw bytes
+                0xfe, 0xff, 0xff, 0xff, // i32 le
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_frame_builder_overflow() {
+        let mut buf = [0u8; 6];
+        let mut builder = FrameBuilder::new(&mut buf);
+
+        assert!(builder.u32_be(1).and_then(|b| b.u32_be(2)).is_err());
+
+        // the field that did not fit is not counted
+        assert_eq!(builder.finish(), 4);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_tracing_writer_log() {
+        let mut buf = [0u8; 8];
+        let mut writer = TracingWriter::new(EndianWriter::new(&mut buf));
+
+        writer
+            .write_field("version", 2u16, EndiannessType::BigEndian)
+            .unwrap();
+        writer
+            .write_field("length", 0x1234u32, EndiannessType::LittleEndian)
+            .unwrap();
+
+        // the failed write is not recorded
+        assert!(writer
+            .write_field("overflow", 0u32, EndiannessType::BigEndian)
+            .is_err());
+
+        assert_eq!(
+            writer.into_log(),
+            [
+                (String::from("version"), 0, 2, EndiannessType::BigEndian),
+                (
+                    String::from("length"),
+                    2,
+                    0x1234,
+                    EndiannessType::LittleEndian
+                )
+            ]
+        );
+        assert_eq!(buf[..6], [0x00, 0x02, 0x34, 0x12, 0x00, 0x00]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_tracing_writer_signed() {
+        let mut buf = [0u8; 16];
+        let mut writer = TracingWriter::new(EndianWriter::new(&mut buf));
+
+        writer
+            .write_field("delta", -2i16, EndiannessType::BigEndian)
+            .unwrap();
+        writer
+            .write_field("offset", -1i32, EndiannessType::LittleEndian)
+            .unwrap();
+        writer
+            .write_field("timestamp", i64::MIN, EndiannessType::BigEndian)
+            .unwrap();
+
+        // signed values are logged as their bits, not sign extended
+        assert_eq!(
+            writer.log(),
+            [
+                (String::from("delta"), 0, 0xfffe, EndiannessType::BigEndian),
+                (
+                    String::from("offset"),
+                    2,
+                    0xffff_ffff,
+                    EndiannessType::LittleEndian
+                ),
+                (
+                    String::from("timestamp"),
+                    6,
+                    0x8000_0000_0000_0000,
+                    EndiannessType::BigEndian
+                )
+            ]
+        );
+
+        // a value too wide for the log is rejected before anything is written
+        assert_eq!(
+            writer.write_field("wide", 0u128, EndiannessType::BigEndian),
+            Err(EndianError::InvalidWidth(16))
+        );
+        assert_eq!(writer.log().len(), 3);
+    }
+}